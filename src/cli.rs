@@ -1,4 +1,9 @@
+use crate::output::require::{parse_requirement, Requirement};
+use crate::render::annotations::Annotations;
+use crate::util::format::{parse_duration, parse_size};
 use clap::{Parser, ValueEnum};
+use std::path::Path;
+use std::time::Duration;
 
 pub const VERSION: &str = "0.9.2";
 
@@ -32,6 +37,107 @@ pub enum StatsMode {
     Full,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ExtCase {
+    /// Lowercase extensions so `.MD` and `.md` are counted together
+    Fold,
+    /// Keep extensions as written
+    Preserve,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputMode {
+    /// Terminal output on a TTY, pipe output otherwise
+    Auto,
+    /// Always use the terminal renderer, even when piped
+    Tty,
+    /// Always use the plain pipe renderer
+    Pipe,
+    /// Markdown table of files: path, type, LOC and size
+    MdTable,
+    /// Nested XML `<directory>`/`<file>` elements
+    Xml,
+    /// gron-style assignments (`tree.src.files[0].name = "main.rs";`)
+    Gron,
+    /// reStructuredText nested bullet list of links
+    Rst,
+    /// Nested TOML tables keyed by directory
+    Toml,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum TrailingSlash {
+    /// Render directories as `name/`
+    Always,
+    /// Render directories as bare `name`
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ColorBy {
+    /// Color file names by file type category
+    Type,
+    /// Color file names by nesting depth
+    Depth,
+    /// Color file names on a heat gradient by size
+    Size,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum NumberFormat {
+    /// Raw integers (`1234`)
+    Plain,
+    /// Thousands separators (`1,234`)
+    Grouped,
+    /// K/M suffixes (`1.2K`)
+    Compact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum AggregateBy {
+    /// Only the global stats section
+    Type,
+    /// Also a file/LOC subtotal beneath each directory in the tree
+    Dir,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum StatsMetric {
+    /// Bars show each type's share of files
+    Files,
+    /// Bars show each type's share of lines of code
+    Loc,
+}
+
+/// Minimum share a file type needs to get its own row in `--stats full`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatsThreshold {
+    /// At least this percentage of all files (`5%`)
+    Percent(f32),
+    /// At least this many files (`3`)
+    Count(usize),
+}
+
+impl std::str::FromStr for StatsThreshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(pct) = s.strip_suffix('%') {
+            pct.trim()
+                .parse::<f32>()
+                .ok()
+                .filter(|p| (0.0..=100.0).contains(p))
+                .map(StatsThreshold::Percent)
+                .ok_or_else(|| format!("invalid percentage '{}'", s))
+        } else {
+            s.parse::<usize>()
+                .map(StatsThreshold::Count)
+                .map_err(|_| format!("invalid threshold '{}' (expected e.g. 3 or 5%)", s))
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, ValueEnum)]
 pub enum LocMode {
     /// Don't count lines of code
@@ -50,6 +156,16 @@ pub enum ContentsMode {
     Nest,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ContentEncoding {
+    /// Skip files that are not valid UTF-8
+    Utf8,
+    /// Decode non-UTF-8 files as Latin-1 (ISO-8859-1)
+    Latin1,
+    /// Decode non-UTF-8 files as UTF-8, replacing invalid bytes with U+FFFD
+    Lossy,
+}
+
 #[derive(Parser, Clone)]
 #[command(name = "tree2md")]
 #[command(version = VERSION)]
@@ -81,9 +197,13 @@ SAFETY:
   Use -I patterns to selectively include filtered items"#
 )]
 pub struct Args {
-    /// Target directory to scan
-    #[arg(default_value = ".", value_name = "TARGET")]
-    pub target: String,
+    /// Target directories to scan (default: .); several are shown under their common ancestor
+    #[arg(value_name = "TARGET")]
+    pub target: Vec<String>,
+
+    /// Target directory to scan (alternative to the positional TARGET)
+    #[arg(long = "root", value_name = "DIR")]
+    pub root: Option<String>,
 
     // ==================== Filtering Options ====================
     /// Limit traversal depth (e.g., -L 3 for max 3 levels deep)
@@ -123,12 +243,190 @@ pub struct Args {
     )]
     pub use_gitignore: UseGitignoreMode,
 
+    /// Drop files larger than SIZE (e.g., 500K, 2M) before they are counted or read
+    #[arg(
+        long = "exclude-larger-than",
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help_heading = "Filtering"
+    )]
+    pub exclude_larger_than: Option<u64>,
+
+    /// Keep only files modified within DURATION (e.g., 12h, 7d, 2w)
+    #[arg(
+        long = "modified-since",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        help_heading = "Filtering"
+    )]
+    pub modified_since: Option<Duration>,
+
+    /// Keep only files last modified more than DURATION ago (e.g., 30d)
+    #[arg(
+        long = "older-than",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        help_heading = "Filtering"
+    )]
+    pub older_than: Option<Duration>,
+
+    /// Exclude files marked `linguist-generated` in .gitattributes
+    #[arg(long = "exclude-generated", help_heading = "Filtering")]
+    pub exclude_generated: bool,
+
+    /// Keep gitignored files in the tree, marked as (ignored)
+    #[arg(long = "include-gitignored", help_heading = "Filtering")]
+    pub include_gitignored: bool,
+
+    /// Only load .gitignore files from the target and below, not its parent directories
+    #[arg(long = "ignore-parent-gitignore", help_heading = "Filtering")]
+    pub ignore_parent_gitignore: bool,
+
+    /// List symlinks as `link -> target` entries (never followed)
+    #[arg(long = "show-symlinks", help_heading = "Filtering")]
+    pub show_symlinks: bool,
+
+    /// Respect `.ignore` files (as used by ripgrep and fd) in addition to .gitignore
+    #[arg(long = "respect-ignore", help_heading = "Filtering")]
+    pub respect_ignore: bool,
+
+    /// Also apply FILE as gitignore rules relative to the target (repeatable)
+    #[arg(long = "extra-ignore", value_name = "FILE", help_heading = "Filtering")]
+    pub extra_ignore: Vec<String>,
+
+    /// Show git submodules listed in .gitmodules without their contents
+    #[arg(long = "exclude-submodules", help_heading = "Filtering")]
+    pub exclude_submodules: bool,
+
+    /// Hide directories whose name starts with `.` while keeping hidden files
+    #[arg(long = "exclude-dot-dirs", help_heading = "Filtering")]
+    pub exclude_dot_dirs: bool,
+
+    /// Print which rule includes or excludes PATH (relative to the target), then exit
+    #[arg(long = "explain", value_name = "PATH", help_heading = "Filtering")]
+    pub explain: Option<String>,
+
+    /// Show at most N entries per directory (alphabetically first), noting the rest
+    #[arg(
+        long = "max-children-per-dir",
+        value_name = "N",
+        help_heading = "Filtering"
+    )]
+    pub max_children_per_dir: Option<usize>,
+
+    /// Don't descend into directories holding more than N files; show them collapsed
+    #[arg(long = "prune-dirs-over", value_name = "N", help_heading = "Filtering")]
+    pub prune_dirs_over: Option<usize>,
+
+    /// Follow symlinks into their targets (cycles are detected and skipped)
+    #[arg(
+        long = "follow-symlinks",
+        conflicts_with = "show_symlinks",
+        help_heading = "Filtering"
+    )]
+    pub follow_symlinks: bool,
+
+    /// List files before directories
+    #[arg(long = "group-dirs-last", help_heading = "Fun & Style")]
+    pub group_dirs_last: bool,
+
+    /// Nest the first N levels, then list deeper files by relative path (`util/mod.rs`)
+    #[arg(long = "flatten-depth", value_name = "N", help_heading = "Filtering")]
+    pub flatten_depth: Option<usize>,
+
+    /// Move test files into a `tests (N)` node in each directory
+    #[arg(long = "group-tests", help_heading = "Fun & Style")]
+    pub group_tests: bool,
+
+    /// Hide files with 0 counted lines (needs --loc fast|accurate)
+    #[arg(long = "exclude-zero-loc", help_heading = "Filtering")]
+    pub exclude_zero_loc: bool,
+
+    /// Hide files with fewer than N counted lines (needs --loc fast|accurate)
+    #[arg(long = "min-loc", value_name = "N", help_heading = "Filtering")]
+    pub min_loc: Option<usize>,
+
+    /// Hide files with more than N counted lines (needs --loc fast|accurate)
+    #[arg(long = "max-loc", value_name = "N", help_heading = "Filtering")]
+    pub max_loc: Option<usize>,
+
+    /// Show only the directory skeleton (no files)
+    #[arg(long = "only-dirs", help_heading = "Filtering")]
+    pub only_dirs: bool,
+
+    /// List file paths only, one per line, sorted (no tree, no stats)
+    #[arg(
+        long = "only-files",
+        conflicts_with = "only_dirs",
+        help_heading = "Filtering"
+    )]
+    pub only_files: bool,
+
+    /// Show node_modules, target, .venv and dist as leaves with their file count
+    #[arg(long = "summarize-known-dirs", help_heading = "Filtering")]
+    pub summarize_known_dirs: bool,
+
+    /// Directory name to summarize instead of the built-in set (repeatable)
+    #[arg(
+        long = "known-dir",
+        value_name = "NAME",
+        requires = "summarize_known_dirs",
+        help_heading = "Filtering"
+    )]
+    pub known_dir: Vec<String>,
+
+    /// Show a reproducible pseudo-random subset of N files (with their parent dirs)
+    #[arg(long = "sample", value_name = "N", help_heading = "Filtering")]
+    pub sample: Option<usize>,
+
+    /// Seed for --sample; the same seed picks the same files
+    #[arg(
+        long = "seed",
+        value_name = "SEED",
+        default_value_t = 0,
+        requires = "sample",
+        help_heading = "Filtering"
+    )]
+    pub seed: u64,
+
+    /// Skip entries whose displayed path is longer than N characters, with a warning
+    #[arg(long = "max-path-length", value_name = "N", help_heading = "Filtering")]
+    pub max_path_length: Option<usize>,
+
+    /// Print how many entries were hidden, by reason, to stderr
+    #[arg(long = "show-ignored-count", help_heading = "Filtering")]
+    pub show_ignored_count: bool,
+
+    /// Hide lockfiles (Cargo.lock, yarn.lock, package-lock.json, ...)
+    #[arg(long = "hide-lockfiles", help_heading = "Filtering")]
+    pub hide_lockfiles: bool,
+
+    /// Match -I/-X globs case-sensitively (the default on Linux; Windows and macOS fold case)
+    #[arg(long = "case-sensitive", help_heading = "Filtering")]
+    pub case_sensitive: bool,
+
+    /// Like --only-files, but end each path with NUL instead of a newline (for `xargs -0`)
+    #[arg(
+        long = "print0",
+        conflicts_with = "only_dirs",
+        help_heading = "Filtering"
+    )]
+    pub print0: bool,
+
     // ==================== Fun & Emojis ====================
     /// Custom emoji mappings (e.g., --emoji ".rs=🚀" --emoji "test=🧪")
     #[arg(long = "emoji", value_name = "MAPPING", help_heading = "Fun & Style")]
     pub emoji: Vec<String>,
 
-    /// Load emoji mappings from TOML file
+    /// Emoji for files of unknown type (default: 📄)
+    #[arg(
+        long = "emoji-for-unknown",
+        value_name = "GLYPH",
+        help_heading = "Fun & Style"
+    )]
+    pub emoji_for_unknown: Option<String>,
+
+    /// Load emoji mappings from TOML file (default: $TREE2MD_EMOJI_MAP, TOML or `.rs=🦀;docs/=📚`)
     #[arg(long = "emoji-map", value_name = "FILE", help_heading = "Fun & Style")]
     pub emoji_map: Option<String>,
 
@@ -141,6 +439,79 @@ pub struct Args {
     )]
     pub fun: FunMode,
 
+    /// De-emphasize generated files (dimmed in TTY, `(generated)` otherwise)
+    #[arg(long = "dim-generated", help_heading = "Fun & Style")]
+    pub dim_generated: bool,
+
+    /// Globs marking generated files (default: *.lock, *.min.js, dist/**, ...)
+    #[arg(
+        long = "generated-glob",
+        value_name = "GLOB",
+        requires = "dim_generated",
+        help_heading = "Fun & Style"
+    )]
+    pub generated_glob: Vec<String>,
+
+    /// Truncate displayed names longer than N characters with a middle ellipsis
+    #[arg(
+        long = "max-name-length",
+        value_name = "N",
+        help_heading = "Fun & Style"
+    )]
+    pub max_name_length: Option<usize>,
+
+    /// Trailing `/` on directory names: always|never (default: always)
+    #[arg(
+        long = "trailing-slash",
+        value_enum,
+        default_value = "always",
+        help_heading = "Fun & Style"
+    )]
+    pub trailing_slash: TrailingSlash,
+
+    /// Emphasize files matching GLOB: `**name**` in pipe output, ⭐ on a TTY (repeatable)
+    #[arg(long = "highlight", value_name = "GLOB", help_heading = "Fun & Style")]
+    pub highlight: Vec<String>,
+
+    /// Show file names without their extension (`main` for `main.rs`); paths are unchanged
+    #[arg(long = "strip-extension", help_heading = "Fun & Style")]
+    pub strip_extension: bool,
+
+    /// Append type indicators like `ls -F`: / dirs, * executables, @ symlinks
+    #[arg(long = "classify", help_heading = "Fun & Style")]
+    pub classify: bool,
+
+    /// Append descriptions from a `path: description` file (keys may be globs)
+    #[arg(
+        long = "annotate-from",
+        value_name = "FILE",
+        help_heading = "Fun & Style"
+    )]
+    pub annotate_from: Option<String>,
+
+    /// The `--annotate-from` file, read once by `load_annotations`
+    #[arg(skip)]
+    pub annotations: Option<Annotations>,
+
+    /// Tint each nesting level with a different color (TTY only, respects NO_COLOR)
+    #[arg(long = "depth-colors", help_heading = "Fun & Style")]
+    pub depth_colors: bool,
+
+    /// Color file names by type, depth or size (TTY only, respects NO_COLOR)
+    #[arg(
+        long = "color-by",
+        value_enum,
+        value_name = "MODE",
+        num_args = 0..=1,
+        default_missing_value = "type",
+        help_heading = "Fun & Style"
+    )]
+    pub color_by: Option<ColorBy>,
+
+    /// Prefix each entry with its nesting level (`[L0]` for the root's children)
+    #[arg(long = "depth-labels", help_heading = "Fun & Style")]
+    pub depth_labels: bool,
+
     /// Disable animations
     #[arg(long = "no-anim", conflicts_with = "fun", help_heading = "Fun & Style")]
     pub no_anim: bool,
@@ -155,6 +526,77 @@ pub struct Args {
     )]
     pub stats: StatsMode,
 
+    /// Deprecated alias for `--stats off`
+    #[arg(long = "no-stats", hide = true)]
+    pub no_stats: bool,
+
+    /// Omit the stats block when no files remain after filtering
+    #[arg(long = "no-empty-stats", help_heading = "Statistics")]
+    pub no_empty_stats: bool,
+
+    /// Extension grouping in stats: fold|preserve (default: fold)
+    #[arg(
+        long = "ext-case",
+        value_enum,
+        default_value = "fold",
+        help_heading = "Statistics"
+    )]
+    pub ext_case: ExtCase,
+
+    /// Number style for stats counts and LOC: plain|grouped|compact
+    /// (default: plain counts, compact LOC)
+    #[arg(
+        long = "number-format",
+        value_enum,
+        value_name = "STYLE",
+        help_heading = "Statistics"
+    )]
+    pub number_format: Option<NumberFormat>,
+
+    /// What the `By type` bars measure in full stats: files|loc
+    #[arg(
+        long = "stats-metric",
+        value_enum,
+        default_value = "files",
+        value_name = "METRIC",
+        help_heading = "Statistics"
+    )]
+    pub stats_metric: StatsMetric,
+
+    /// Where totals appear: type (global stats only) or dir (also beneath each directory)
+    #[arg(
+        long = "aggregate-by",
+        value_enum,
+        default_value = "type",
+        value_name = "SCOPE",
+        help_heading = "Statistics"
+    )]
+    pub aggregate_by: AggregateBy,
+
+    /// With --aggregate-by dir, only show subtotals for directories with more than N files
+    #[arg(
+        long = "count-badge-threshold",
+        value_name = "N",
+        help_heading = "Statistics"
+    )]
+    pub count_badge_threshold: Option<usize>,
+
+    /// Append a files-per-depth histogram (`L0:3 L1:12 L2:45`) to the stats
+    #[arg(long = "depth-summary", help_heading = "Statistics")]
+    pub depth_summary: bool,
+
+    /// Leave extensionless files out of the by-extension stats breakdown
+    #[arg(long = "stats-skip-no-ext", help_heading = "Statistics")]
+    pub stats_skip_no_ext: bool,
+
+    /// Roll file types below N files (or N%) into an "Other" row in full stats
+    #[arg(
+        long = "stats-threshold",
+        value_name = "N",
+        help_heading = "Statistics"
+    )]
+    pub stats_threshold: Option<StatsThreshold>,
+
     /// Line counting mode: off|fast|accurate
     #[arg(
         long = "loc",
@@ -164,6 +606,15 @@ pub struct Args {
     )]
     pub loc: LocMode,
 
+    /// Skip line counting for files larger than SIZE (e.g., 500K, 2M)
+    #[arg(
+        long = "max-file-size-for-loc",
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help_heading = "Statistics"
+    )]
+    pub max_file_size_for_loc: Option<u64>,
+
     // ==================== Contents ====================
     /// Include file contents as code blocks (for AI context)
     #[arg(short = 'c', long = "contents")]
@@ -187,6 +638,103 @@ pub struct Args {
     )]
     pub contents_mode: ContentsMode,
 
+    /// How to read files that are not valid UTF-8: utf8 (skip), latin1, lossy
+    #[arg(
+        long = "encoding",
+        value_enum,
+        default_value = "utf8",
+        value_name = "ENCODING",
+        help_heading = "Contents"
+    )]
+    pub encoding: ContentEncoding,
+
+    /// Note appended to truncated files; placeholders: {shown}, {total}, {omitted}
+    #[arg(
+        long = "truncation-note",
+        value_name = "STR",
+        default_value = "... ({omitted} lines omitted)",
+        help_heading = "Contents"
+    )]
+    pub truncation_note: String,
+
+    /// Force the code fence language for matching file names (e.g., ".env.example=bash", "*.tpl=html")
+    #[arg(
+        long = "contents-lang",
+        value_name = "GLOB=LANG",
+        value_parser = parse_lang_override,
+        help_heading = "Contents"
+    )]
+    pub contents_lang: Vec<(String, String)>,
+
+    // ==================== Output ====================
+    /// Output style: auto|tty|pipe|md-table|xml|gron|rst|toml (default: auto, based on TTY detection)
+    #[arg(
+        long = "output",
+        value_enum,
+        default_value = "auto",
+        value_name = "MODE",
+        help_heading = "Output"
+    )]
+    pub output: OutputMode,
+
+    /// Rendering width in columns for the tty tree (default: $COLUMNS, or the
+    /// terminal's width)
+    #[arg(long = "term-width", value_name = "N", help_heading = "Output")]
+    pub term_width: Option<usize>,
+
+    /// Byte-reproducible output for CI: pipe output unless --output is given,
+    /// no fun mode, no colors, and an 80-column width unless --term-width is given
+    #[arg(long = "deterministic", help_heading = "Output")]
+    pub deterministic: bool,
+
+    /// Render the root directory name as a markdown heading of LEVEL (1-6) above the tree
+    #[arg(
+        long = "render-root-as-heading",
+        value_name = "LEVEL",
+        value_parser = clap::value_parser!(u8).range(1..=6),
+        help_heading = "Output"
+    )]
+    pub render_root_as_heading: Option<u8>,
+
+    /// Glyph shown before the root name in the heading (e.g., a project logo)
+    #[arg(
+        long = "root-emoji",
+        value_name = "GLYPH",
+        requires = "render_root_as_heading",
+        help_heading = "Output"
+    )]
+    pub root_emoji: Option<String>,
+
+    /// Show NAME instead of the scanned directory's name wherever the root is rendered
+    #[arg(long = "rename-root", value_name = "NAME", help_heading = "Output")]
+    pub rename_root: Option<String>,
+
+    /// Exit with status 1 when no files remain after filtering
+    #[arg(long = "fail-on-empty", help_heading = "Output")]
+    pub fail_on_empty: bool,
+
+    /// Exit with status 1 unless the stats satisfy PRED: files|dirs|loc|.EXT with >=, <=, ==, !=, >, < (repeatable)
+    #[arg(
+        long = "require",
+        value_name = "PRED",
+        value_parser = parse_requirement,
+        help_heading = "Output"
+    )]
+    pub require: Vec<Requirement>,
+
+    /// Compare output against a snapshot file and exit non-zero with a diff if it differs
+    #[arg(long = "check", value_name = "SNAPSHOT", help_heading = "Output")]
+    pub check: Option<String>,
+
+    /// Write one markdown file per top-level directory into DIR, plus an index.md
+    #[arg(
+        long = "split-output",
+        value_name = "DIR",
+        conflicts_with = "check",
+        help_heading = "Output"
+    )]
+    pub split_output: Option<String>,
+
     // ==================== Safety & Security ====================
     /// Apply safety filters (enabled by default)
     #[arg(long = "safe", help_heading = "Safety")]
@@ -198,14 +746,101 @@ pub struct Args {
 }
 
 impl Args {
+    /// Directory to scan, resolved from TARGET or `--root` (the first of several TARGETs)
+    pub fn target_dir(&self) -> &str {
+        self.target.first().map_or(".", String::as_str)
+    }
+
+    /// Validate argument combinations that clap cannot express on its own
+    pub fn validate(&mut self) -> Result<(), String> {
+        if let Some(root) = self.root.take() {
+            match self.target.as_slice() {
+                [] => {}
+                [target] if same_path(target, &root) => {}
+                [target] => {
+                    return Err(format!(
+                        "TARGET '{}' and --root '{}' refer to different directories",
+                        target, root
+                    ));
+                }
+                _ => return Err("--root cannot be combined with several TARGETs".to_string()),
+            }
+            self.target = vec![root];
+        }
+        if self.count_badge_threshold.is_some() && self.aggregate_by != AggregateBy::Dir {
+            return Err("--count-badge-threshold requires --aggregate-by dir".to_string());
+        }
+        Ok(())
+    }
+
     /// Determine if safe mode is enabled (default: true)
     pub fn is_safe_mode(&self) -> bool {
         !self.unsafe_mode
     }
 
-    /// Check if stats should be shown
-    pub fn should_show_stats(&self) -> bool {
-        self.stats != StatsMode::Off
+    /// Warn about deprecated flags and map them onto their replacements
+    pub fn check_deprecated(&mut self) {
+        if self.no_stats {
+            eprintln!("Warning: --no-stats is deprecated; use --stats off instead");
+            self.stats = StatsMode::Off;
+        }
+    }
+
+    /// Read the `--annotate-from` file, warning once if it can't be loaded.
+    /// Every renderer then shares the same annotations.
+    pub fn load_annotations(&mut self) {
+        self.annotations = self.annotate_from.as_ref().and_then(|path| {
+            Annotations::load(Path::new(path))
+                .map_err(|e| eprintln!("Warning: Failed to load annotations from {}: {}", path, e))
+                .ok()
+        });
+    }
+
+    /// Pin everything `--deterministic` makes independent of the environment
+    pub fn apply_deterministic(&mut self) {
+        if !self.deterministic {
+            return;
+        }
+        if self.output == OutputMode::Auto {
+            self.output = OutputMode::Pipe;
+        }
+        if self.fun == FunMode::Auto {
+            self.fun = FunMode::Off;
+        }
+        self.term_width.get_or_insert(80);
+        self.depth_colors = false;
+        self.color_by = None;
+    }
+
+    /// Suffix appended to directory names in tree output
+    pub fn dir_suffix(&self) -> &'static str {
+        if self.classify {
+            return "/";
+        }
+        match self.trailing_slash {
+            TrailingSlash::Always => "/",
+            TrailingSlash::Never => "",
+        }
+    }
+
+    /// `[Ln] ` label for an entry at nesting `depth` (0 under the root),
+    /// empty unless `--depth-labels`
+    pub fn depth_label(&self, depth: usize) -> String {
+        if !self.depth_labels {
+            return String::new();
+        }
+        format!("[L{}] ", depth)
+    }
+
+    /// Whether to append the stats block to a tree holding `files` files
+    pub fn should_show_stats(&self, files: usize) -> bool {
+        self.stats != StatsMode::Off && !(self.no_empty_stats && files == 0)
+    }
+
+    /// Whether `--aggregate-by dir` shows a subtotal for a directory holding `files` files
+    pub fn shows_dir_subtotal(&self, files: usize) -> bool {
+        self.aggregate_by == AggregateBy::Dir
+            && self.count_badge_threshold.is_none_or(|min| files > min)
     }
 
     /// Check if fun mode is enabled
@@ -217,3 +852,21 @@ impl Args {
         }
     }
 }
+
+/// Parse a `GLOB=LANG` fence language override
+fn parse_lang_override(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((glob, lang)) if !glob.is_empty() && !lang.is_empty() => {
+            Ok((glob.to_string(), lang.to_string()))
+        }
+        _ => Err(format!("expected GLOB=LANG, got '{}'", s)),
+    }
+}
+
+/// Compare two user-supplied paths, resolving them when they exist
+fn same_path(a: &str, b: &str) -> bool {
+    match (Path::new(a).canonicalize(), Path::new(b).canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
@@ -1,7 +1,11 @@
+use crate::cli::ContentEncoding;
 use std::fs::File;
 use std::io::{self, Read};
 use std::path::Path;
 
+/// Bytes inspected when sniffing for binary content
+pub const PROBE_SIZE: usize = 8192;
+
 /// Result of probing a file for binary/text characteristics
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -21,13 +25,7 @@ pub fn probe_file(path: &Path, max_probe: usize) -> io::Result<ProbeResult> {
     let n = file.read(&mut probe_buf)?;
     probe_buf.truncate(n);
 
-    let has_null = probe_buf.contains(&0);
-    let control_chars = probe_buf
-        .iter()
-        .filter(|&&b| b < 32 && b != 9 && b != 10 && b != 13)
-        .count();
-
-    let is_binary = has_null || (control_chars > n / 10);
+    let is_binary = looks_binary(&probe_buf);
     let is_utf8 = std::str::from_utf8(&probe_buf).is_ok();
 
     Ok(ProbeResult {
@@ -37,6 +35,40 @@ pub fn probe_file(path: &Path, max_probe: usize) -> io::Result<ProbeResult> {
     })
 }
 
+/// Binary heuristic shared by `probe_file` and `read_text`: a NUL byte, or
+/// more than 10% control characters other than tab, LF and CR
+fn looks_binary(sample: &[u8]) -> bool {
+    let control_chars = sample
+        .iter()
+        .filter(|&&b| b < 32 && b != 9 && b != 10 && b != 13)
+        .count();
+    sample.contains(&0) || control_chars > sample.len() / 10
+}
+
+/// Read a file as text. Binaries (by the `probe_file` heuristic) are always
+/// skipped; other files that are not valid UTF-8 are skipped under `utf8`
+/// and decoded under `latin1`/`lossy`. The flag reports whether the file
+/// needed decoding.
+pub fn read_text(path: &Path, encoding: ContentEncoding) -> Option<(String, bool)> {
+    let bytes = std::fs::read(path).ok()?;
+    if looks_binary(&bytes[..bytes.len().min(PROBE_SIZE)]) {
+        return None;
+    }
+    match String::from_utf8(bytes) {
+        Ok(text) => Some((text, false)),
+        Err(err) => {
+            let bytes = err.into_bytes();
+            let text = match encoding {
+                ContentEncoding::Utf8 => return None,
+                // Latin-1 maps each byte to the code point of the same value
+                ContentEncoding::Latin1 => bytes.iter().map(|&b| char::from(b)).collect(),
+                ContentEncoding::Lossy => String::from_utf8_lossy(&bytes).into_owned(),
+            };
+            Some((text, true))
+        }
+    }
+}
+
 /// Check if a file is too large based on size limit
 #[allow(dead_code)]
 pub fn is_too_large(path: &Path, max_size: u64) -> bool {
@@ -93,6 +125,16 @@ mod tests {
         assert!(result.is_binary);
     }
 
+    #[test]
+    fn test_read_text_skips_binary() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.out");
+        fs::write(&path, b"ELF\0\x01\x02").unwrap();
+
+        assert!(read_text(&path, ContentEncoding::Latin1).is_none());
+        assert!(read_text(&path, ContentEncoding::Lossy).is_none());
+    }
+
     #[test]
     fn test_is_too_large() {
         let dir = tempdir().unwrap();
@@ -12,6 +12,9 @@ pub fn truncate_head_lines(content: &str, n: usize) -> (String, usize) {
 
 /// Find the largest n such that taking the first n lines of each file
 /// keeps total chars <= max_chars. Uses binary search.
+///
+/// The budget counts Unicode scalar values (`chars()`), not bytes, so
+/// multibyte text gets the same allowance as ASCII.
 pub fn find_head_n(file_contents: &[&str], max_chars: usize) -> usize {
     if file_contents.is_empty() {
         return 0;
@@ -49,7 +52,7 @@ fn total_chars_at_head_n(file_contents: &[&str], n: usize) -> usize {
         .iter()
         .map(|content| {
             let (truncated, _) = truncate_head_lines(content, n);
-            truncated.len()
+            truncated.chars().count()
         })
         .sum()
 }
@@ -116,7 +119,7 @@ pub fn find_nest_threshold(file_contents: &[&str], max_chars: usize) -> Option<u
     for threshold in (0..=max_indent).rev() {
         let total: usize = file_lines
             .iter()
-            .map(|lines| collapse_at_indent(lines, threshold).0.len())
+            .map(|lines| collapse_at_indent(lines, threshold).0.chars().count())
             .sum();
         if total <= max_chars {
             return Some(threshold);
@@ -176,6 +179,18 @@ mod tests {
         assert_eq!(n, 2); // n=2 => 18 <= 20, n=3 => 28 > 20
     }
 
+    #[test]
+    fn test_find_head_n_counts_chars_not_bytes() {
+        // Each line is 3 chars but 9 bytes
+        let files = vec![
+            "日本語
+日本語
+日本語",
+        ];
+        assert_eq!(find_head_n(&files, 11), 3); // 3*3 + 2 newlines = 11 chars
+        assert_eq!(find_head_n(&files, 10), 2);
+    }
+
     #[test]
     fn test_find_head_n_uniform() {
         // All files get the same n
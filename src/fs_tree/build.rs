@@ -1,7 +1,8 @@
 use super::node::Node;
+use super::sample::sample_files;
 use crate::cli::Args;
-use crate::matcher::{MatchSpec, MatcherEngine, RelPath, Selection};
-use crate::util::path::calculate_display_path;
+use crate::matcher::{MatchSpec, MatcherEngine, Reason, RelPath, Selection};
+use crate::util::path::{calculate_display_path, common_ancestor};
 use ignore::WalkBuilder;
 use std::collections::HashMap;
 use std::fs;
@@ -20,6 +21,80 @@ pub fn build_tree(
     build_tree_with_spec(path, args, &spec, root_path, display_root)
 }
 
+/// Build one tree from several targets, shown under their common ancestor.
+/// Each target is walked with its own filtering (gitignore, patterns
+/// relative to it); a target inside another one is covered by the outer walk.
+pub fn build_multi_tree(targets: &[String], args: &Args) -> io::Result<Node> {
+    let mut roots: Vec<(PathBuf, &str)> = targets
+        .iter()
+        .map(|t| Ok((Path::new(t).canonicalize()?, t.as_str())))
+        .collect::<io::Result<_>>()?;
+    roots.sort();
+    roots.dedup_by(|inner, outer| inner.0.starts_with(&outer.0));
+
+    let paths: Vec<PathBuf> = roots.iter().map(|(path, _)| path.clone()).collect();
+    let ancestor = common_ancestor(&paths);
+    let name = ancestor
+        .file_name()
+        .map_or_else(|| ".".to_string(), |n| n.to_string_lossy().to_string());
+    let mut root_node = Node::new(name, ancestor.clone(), true)
+        .with_display_path(calculate_display_path(&ancestor, &ancestor));
+
+    let mut hidden = HiddenCounts::default();
+    for (path, target) in &roots {
+        let spec = MatchSpec::from_args(args, Path::new(target));
+        let node = walk_tree(target, args, &spec, path, &ancestor, &mut hidden)?;
+        let rel = path.strip_prefix(&ancestor).unwrap_or(path);
+        graft(&mut root_node, node, rel, args.group_dirs_last);
+    }
+
+    // Sample and report once over the merged tree, not per target
+    finish_tree(&mut root_node, args, &hidden);
+    Ok(root_node)
+}
+
+/// Place `node` at `rel` below `parent`, creating the directories in between
+fn graft(parent: &mut Node, node: Node, rel: &Path, dirs_last: bool) {
+    let mut components = rel.components();
+    let Some(first) = components.next() else {
+        // The target is the ancestor itself
+        parent.children.extend(node.children);
+        return;
+    };
+    let rest = components.as_path();
+
+    if rest.as_os_str().is_empty() {
+        parent.children.push(node);
+    } else {
+        let name = first.as_os_str().to_string_lossy().to_string();
+        let index = match parent
+            .children
+            .iter()
+            .position(|c| c.is_dir && c.name == name)
+        {
+            Some(index) => index,
+            None => {
+                let path = parent.path.join(&name);
+                let display_path = parent.display_path.join(&name);
+                parent
+                    .children
+                    .push(Node::new(name, path, true).with_display_path(display_path));
+                parent.children.len() - 1
+            }
+        };
+        graft(&mut parent.children[index], node, rest, dirs_last);
+    }
+
+    // Same order as build_tree_from_map: dirs first (or last), then by name
+    parent.children.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+        (true, false) if dirs_last => std::cmp::Ordering::Greater,
+        (false, true) if dirs_last => std::cmp::Ordering::Less,
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        _ => a.name.cmp(&b.name),
+    });
+}
+
 /// Build tree using the MatcherEngine architecture
 pub fn build_tree_with_spec(
     path: &str,
@@ -27,6 +102,37 @@ pub fn build_tree_with_spec(
     spec: &MatchSpec,
     root_path: &Path,
     display_root: &Path,
+) -> io::Result<Node> {
+    let mut hidden = HiddenCounts::default();
+    let mut root_node = walk_tree(path, args, spec, root_path, display_root, &mut hidden)?;
+    finish_tree(&mut root_node, args, &hidden);
+    Ok(root_node)
+}
+
+/// `--sample` and `--show-ignored-count`, applied once to the finished tree
+fn finish_tree(root_node: &mut Node, args: &Args, hidden: &HiddenCounts) {
+    if !root_node.is_dir {
+        return;
+    }
+
+    if let Some(n) = args.sample {
+        sample_files(root_node, n, args.seed);
+    }
+
+    // --show-ignored-count goes to stderr so stdout stays pipeable
+    if args.show_ignored_count {
+        eprintln!("{}", hidden.summary());
+    }
+}
+
+/// Walk one target, adding what the matcher hides to `hidden`
+fn walk_tree(
+    path: &str,
+    args: &Args,
+    spec: &MatchSpec,
+    root_path: &Path,
+    display_root: &Path,
+    hidden: &mut HiddenCounts,
 ) -> io::Result<Node> {
     let path_buf = Path::new(path);
     let metadata = fs::metadata(path_buf)?;
@@ -58,7 +164,7 @@ pub fn build_tree_with_spec(
             .git_exclude(false)
             .parents(false)
             .ignore(false)
-            .follow_links(false) // Skip symlinks as per spec
+            .follow_links(args.follow_symlinks) // Symlinks are skipped unless --follow-symlinks
             .max_depth(args.level); // Use level directly
 
         // Build a map of paths to nodes for efficient tree construction
@@ -69,7 +175,14 @@ pub fn build_tree_with_spec(
         for entry in walker.build() {
             let entry = match entry {
                 Ok(e) => e,
-                Err(_) => continue,
+                Err(err) => {
+                    // The walker refuses to re-enter a directory it is already inside
+                    // when following links; surface that instead of dropping it silently
+                    if let Some(child) = symlink_loop_child(&err) {
+                        eprintln!("Warning: skipping symlink cycle at {}", child.display());
+                    }
+                    continue;
+                }
             };
 
             let entry_path = entry.path();
@@ -79,8 +192,9 @@ pub fn build_tree_with_spec(
                 continue;
             }
 
-            // Skip symlinks entirely (per spec: "Symlinks are always skipped")
-            if entry.file_type().map(|ft| ft.is_symlink()).unwrap_or(false) {
+            // Skip symlinks unless --show-symlinks asks to list them (never followed)
+            let is_symlink = entry.file_type().map(|ft| ft.is_symlink()).unwrap_or(false);
+            if is_symlink && !args.show_symlinks {
                 continue;
             }
 
@@ -104,31 +218,62 @@ pub fn build_tree_with_spec(
                 Err(_) => continue,
             };
 
+            // Create RelPath for matching. When following links, canonicalizing
+            // would resolve through them, so derive it lexically from the walk root.
+            let rel_path = match entry_path.strip_prefix(path_buf) {
+                Ok(rel) if args.follow_symlinks => RelPath::from_relative(rel),
+                _ => match RelPath::from_root_rel(entry_path, root_path) {
+                    Some(rp) => rp,
+                    None => continue,
+                },
+            };
+
             // Prune nested git repositories / worktrees / submodules.
             // If a subdirectory contains a `.git` entry (file or directory),
             // it represents a separate repository boundary and should not be
             // traversed. This prevents worktrees, submodules, and nested repos
-            // from leaking into the output.
-            if entry_metadata.is_dir() && entry_path.join(".git").exists() {
+            // from leaking into the output. Submodules listed by
+            // `--exclude-submodules` still get their leaf entry below.
+            if entry_metadata.is_dir()
+                && entry_path.join(".git").exists()
+                && !matcher.is_shallow_dir(&rel_path)
+            {
                 pruned_dirs.insert(entry_path.to_path_buf());
                 has_nested_repo_pruning = true;
                 continue;
             }
 
-            // Create RelPath for matching
-            let rel_path = match RelPath::from_root_rel(entry_path, root_path) {
-                Some(rp) => rp,
-                None => continue,
-            };
-
             // Apply matcher engine selection
-            let selection = if entry_metadata.is_dir() {
-                matcher.select_dir(&rel_path)
+            // Sized files also answer --exclude-larger-than, so oversized ones
+            // never reach stats, LOC counting or content dumping
+            let (selection, reason) = if entry_metadata.is_dir() {
+                matcher.decide_dir(&rel_path)
+            } else if entry_metadata.is_file() {
+                matcher.decide_file_with_size(&rel_path, entry_metadata.len())
             } else {
-                matcher.select_file(&rel_path)
+                matcher.decide_file(&rel_path)
             };
 
-            match selection {
+            // --summarize-known-dirs: heavy well-known directories become a
+            // leaf with their total file count, even where the safety preset
+            // would have pruned them
+            let summarized_files = (args.summarize_known_dirs
+                && entry_metadata.is_dir()
+                && (selection == Selection::Include || reason == Reason::Safety)
+                && is_known_heavy_dir(args, entry_path))
+            .then(|| recursive_file_count(entry_path));
+
+            if summarized_files.is_none()
+                && matches!(selection, Selection::PruneDir | Selection::Exclude)
+            {
+                hidden.record(reason);
+            }
+
+            let ignored = match selection {
+                _ if summarized_files.is_some() => {
+                    pruned_dirs.insert(entry_path.to_path_buf());
+                    false
+                }
                 Selection::PruneDir => {
                     // Mark this directory as pruned so we skip its children
                     pruned_dirs.insert(entry_path.to_path_buf());
@@ -137,36 +282,115 @@ pub fn build_tree_with_spec(
                 Selection::Exclude => {
                     continue;
                 }
+                Selection::Ignored => {
+                    // Keep the entry, but never descend into ignored directories
+                    if entry_metadata.is_dir() {
+                        pruned_dirs.insert(entry_path.to_path_buf());
+                    }
+                    true
+                }
+                Selection::Shallow => {
+                    // Keep the directory as a leaf
+                    pruned_dirs.insert(entry_path.to_path_buf());
+                    false
+                }
                 Selection::Include => {
                     // Include this file/dir in the tree
+                    false
+                }
+            };
+
+            // --modified-since / --older-than: drop files outside the mtime window
+            if let (true, Ok(modified)) = (entry_metadata.is_file(), entry_metadata.modified()) {
+                if !matcher.mtime_allowed(modified) {
+                    hidden.out_of_date_range += 1;
+                    continue;
                 }
             }
 
+            // --prune-dirs-over: directories with too many immediate files are
+            // shown as a collapsed leaf instead of being walked
+            let collapsed_files = summarized_files.or(match args.prune_dirs_over {
+                Some(limit) if entry_metadata.is_dir() && !ignored => {
+                    let count = immediate_file_count(entry_path);
+                    (count > limit).then_some(count)
+                }
+                _ => None,
+            });
+            if collapsed_files.is_some() {
+                pruned_dirs.insert(entry_path.to_path_buf());
+            }
+
             let entry_name = entry_path
                 .file_name()
                 .unwrap_or_else(|| std::ffi::OsStr::new("."))
                 .to_string_lossy()
                 .to_string();
 
-            let resolved_entry_path = entry_path
-                .canonicalize()
-                .unwrap_or_else(|_| entry_path.to_path_buf());
+            // Canonicalizing a symlink would resolve it to its target,
+            // so only resolve the parent directory in that case; paths reached
+            // through followed links are built from the canonical root instead
+            let resolved_entry_path = if args.follow_symlinks {
+                entry_path
+                    .strip_prefix(path_buf)
+                    .map(|rel| resolved_path.join(rel))
+                    .unwrap_or_else(|_| entry_path.to_path_buf())
+            } else if is_symlink {
+                entry_path
+                    .parent()
+                    .and_then(|parent| parent.canonicalize().ok())
+                    .map(|parent| parent.join(&entry_name))
+                    .unwrap_or_else(|| entry_path.to_path_buf())
+            } else {
+                entry_path
+                    .canonicalize()
+                    .unwrap_or_else(|_| entry_path.to_path_buf())
+            };
 
             let entry_display_path = calculate_display_path(&resolved_entry_path, display_root);
 
+            // --max-path-length: drop degenerate paths (e.g. deep symlink chains)
+            if let Some(max) = args.max_path_length {
+                let display = entry_display_path.to_string_lossy();
+                if display.chars().count() > max {
+                    eprintln!(
+                        "Warning: skipping path longer than {} characters: {}",
+                        max, display
+                    );
+                    if entry_metadata.is_dir() {
+                        pruned_dirs.insert(entry_path.to_path_buf());
+                    }
+                    hidden.too_long += 1;
+                    continue;
+                }
+            }
+
             let node = Node::new(entry_name, resolved_entry_path, entry_metadata.is_dir())
-                .with_display_path(entry_display_path);
+                .with_display_path(entry_display_path)
+                .with_ignored(ignored)
+                .with_executable(is_executable(&entry_metadata))
+                .with_size(if entry_metadata.is_dir() {
+                    0
+                } else {
+                    entry_metadata.len()
+                })
+                .with_collapsed_files(collapsed_files)
+                .with_symlink_target(if is_symlink {
+                    fs::read_link(entry_path).ok()
+                } else {
+                    None
+                });
 
             nodes_map.insert(entry_path.to_path_buf(), node);
         }
 
         // Build the tree structure from the flat map
-        build_tree_from_map(&mut root_node, &nodes_map, path_buf)?;
+        build_tree_from_map(&mut root_node, &nodes_map, path_buf, args.group_dirs_last)?;
 
         // Remove directories left empty after pruning (include filtering,
-        // nested-repo detection, etc.). Not run unconditionally because
+        // nested-repo detection, mtime windows, etc.). Not run unconditionally because
         // empty dirs at --level boundary should remain visible.
-        if spec.has_includes() || has_nested_repo_pruning {
+        if spec.has_includes() || has_nested_repo_pruning || matcher.has_mtime_range() {
             remove_empty_directories(&mut root_node);
         }
     }
@@ -174,10 +398,56 @@ pub fn build_tree_with_spec(
     Ok(root_node)
 }
 
+/// Entries the matcher (or `--max-path-length`) hid during the walk, for
+/// `--show-ignored-count`.
+/// A pruned directory counts once, not per file inside it.
+#[derive(Debug, Default, PartialEq)]
+struct HiddenCounts {
+    gitignored: usize,
+    safety: usize,
+    excluded: usize,
+    too_long: usize,
+    out_of_date_range: usize,
+}
+
+impl HiddenCounts {
+    fn record(&mut self, reason: Reason) {
+        match reason {
+            Reason::Gitignore | Reason::IgnoreFile => self.gitignored += 1,
+            Reason::Safety => self.safety += 1,
+            // `.git` is always pruned, so reporting it would only be noise
+            Reason::GitDir => {}
+            _ => self.excluded += 1,
+        }
+    }
+
+    /// One line like `Hidden: 120 gitignored, 3 safety, 2 excluded`
+    fn summary(&self) -> String {
+        let parts: Vec<String> = [
+            (self.gitignored, "gitignored"),
+            (self.safety, "safety"),
+            (self.excluded, "excluded"),
+            (self.too_long, "too long"),
+            (self.out_of_date_range, "outside date range"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect();
+
+        if parts.is_empty() {
+            "Hidden: nothing".to_string()
+        } else {
+            format!("Hidden: {}", parts.join(", "))
+        }
+    }
+}
+
 fn build_tree_from_map(
     parent: &mut Node,
     nodes_map: &HashMap<PathBuf, Node>,
     base_path: &Path,
+    dirs_last: bool,
 ) -> io::Result<()> {
     let mut direct_children: Vec<PathBuf> = Vec::new();
 
@@ -190,15 +460,21 @@ fn build_tree_from_map(
         }
     }
 
-    // Sort children: directories first, then files, alphabetically within each group
+    // Sort children: directories first (or last with --group-dirs-last),
+    // alphabetically within each group
     direct_children.sort_by(|a, b| {
         let a_node = nodes_map.get(a).unwrap();
         let b_node = nodes_map.get(b).unwrap();
 
-        match (a_node.is_dir, b_node.is_dir) {
+        let group = match (a_node.is_dir, b_node.is_dir) {
             (true, false) => std::cmp::Ordering::Less,
             (false, true) => std::cmp::Ordering::Greater,
-            _ => a_node.name.cmp(&b_node.name),
+            _ => return a_node.name.cmp(&b_node.name),
+        };
+        if dirs_last {
+            group.reverse()
+        } else {
+            group
         }
     });
 
@@ -207,7 +483,7 @@ fn build_tree_from_map(
         if let Some(child_node) = nodes_map.get(&child_path) {
             let mut child = child_node.clone();
             if child.is_dir {
-                build_tree_from_map(&mut child, nodes_map, &child_path)?;
+                build_tree_from_map(&mut child, nodes_map, &child_path, dirs_last)?;
             }
             parent.children.push(child);
         }
@@ -216,6 +492,75 @@ fn build_tree_from_map(
     Ok(())
 }
 
+/// Number of regular files directly inside `dir`
+fn immediate_file_count(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+/// Directories `--summarize-known-dirs` collapses unless `--known-dir` is given
+const KNOWN_HEAVY_DIRS: &[&str] = &["node_modules", "target", ".venv", "dist"];
+
+/// Whether `dir` is named like a well-known heavy directory
+fn is_known_heavy_dir(args: &Args, dir: &Path) -> bool {
+    let Some(name) = dir.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    if args.known_dir.is_empty() {
+        KNOWN_HEAVY_DIRS.contains(&name)
+    } else {
+        args.known_dir.iter().any(|known| known == name)
+    }
+}
+
+/// Number of regular files anywhere under `dir` (symlinks are not followed)
+fn recursive_file_count(dir: &Path) -> usize {
+    let mut count = 0;
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => stack.push(entry.path()),
+                Ok(t) if t.is_file() => count += 1,
+                _ => {}
+            }
+        }
+    }
+    count
+}
+
+/// Path of the link that closes a cycle, if the walk error is a symlink loop
+fn symlink_loop_child(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::Loop { child, .. } => Some(child),
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => symlink_loop_child(err),
+        _ => None,
+    }
+}
+
+/// Whether a regular file has any execute bit set
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
 /// Remove directories that have no children after filtering.
 /// This is needed because PruneDir only prevents descending, it doesn't remove the directory node.
 fn remove_empty_directories(node: &mut Node) {
@@ -230,9 +575,10 @@ fn remove_empty_directories(node: &mut Node) {
         }
     }
 
-    // Remove empty directory children
-    node.children
-        .retain(|child| !child.is_dir || !child.children.is_empty());
+    // Remove empty directory children (collapsed dirs are empty on purpose)
+    node.children.retain(|child| {
+        !child.is_dir || !child.children.is_empty() || child.collapsed_files.is_some()
+    });
 }
 
 #[cfg(test)]
@@ -244,6 +590,36 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_remove_empty_directories_collapses_chains() {
+        // a/b/c/ (empty chain) next to x/y/keep.rs
+        let c = Node::new("c".into(), PathBuf::from("a/b/c"), true);
+        let mut b = Node::new("b".into(), PathBuf::from("a/b"), true);
+        b.children.push(c);
+        let mut a = Node::new("a".into(), PathBuf::from("a"), true);
+        a.children.push(b);
+
+        let mut y = Node::new("y".into(), PathBuf::from("x/y"), true);
+        y.children.push(Node::new(
+            "keep.rs".into(),
+            PathBuf::from("x/y/keep.rs"),
+            false,
+        ));
+        let mut x = Node::new("x".into(), PathBuf::from("x"), true);
+        x.children.push(y);
+
+        let mut root = Node::new(".".into(), PathBuf::from("."), true);
+        root.children.push(a);
+        root.children.push(x);
+
+        remove_empty_directories(&mut root);
+
+        // Children are processed before their parent, so the whole chain goes
+        assert_eq!(root.children.len(), 1);
+        assert_eq!(root.children[0].name, "x");
+        assert_eq!(root.children[0].children[0].children[0].name, "keep.rs");
+    }
+
     #[test]
     fn test_matcher_engine_integration() {
         let temp_dir = TempDir::new().unwrap();
@@ -261,7 +637,7 @@ mod tests {
         fs::create_dir_all(root.join(".git")).unwrap();
         fs::write(root.join(".gitignore"), "target/").unwrap();
 
-        let args = Args::parse_from(&["tree2md", root.to_str().unwrap()]);
+        let args = Args::parse_from(["tree2md", root.to_str().unwrap()]);
 
         // Test with extension filter
         let spec = MatchSpec::new().with_include_ext(vec![".rs".to_string()]);
@@ -302,7 +678,7 @@ mod tests {
         fs::write(root.join("temp.tmp"), "temporary").unwrap();
         fs::write(root.join("data.txt"), "data").unwrap();
 
-        let args = Args::parse_from(&["tree2md", root.to_str().unwrap()]);
+        let args = Args::parse_from(["tree2md", root.to_str().unwrap()]);
 
         // Test with gitignore enabled
         let spec = MatchSpec::new().with_gitignore(true);
@@ -330,7 +706,7 @@ mod tests {
         fs::write(root.join("test.rs"), "test").unwrap();
         fs::write(root.join("README.md"), "readme").unwrap();
 
-        let args = Args::parse_from(&["tree2md", root.to_str().unwrap()]);
+        let args = Args::parse_from(["tree2md", root.to_str().unwrap()]);
 
         // Test with glob pattern
         let spec =
@@ -41,7 +41,7 @@ impl LocCounter {
         }
 
         // Probe file content to check if it's binary
-        match io::probe_file(path, 8192) {
+        match io::probe_file(path, io::PROBE_SIZE) {
             Ok(probe) => {
                 if probe.is_binary {
                     return None;
@@ -2,8 +2,9 @@ pub mod build;
 pub mod loc;
 pub mod node;
 pub mod progress;
+pub mod sample;
 
-pub use build::build_tree;
+pub use build::{build_multi_tree, build_tree};
 pub use loc::LocCounter;
 pub use node::Node;
 pub use progress::ProgressTracker;
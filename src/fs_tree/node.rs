@@ -1,12 +1,23 @@
 use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Node {
     pub name: String,
     pub path: PathBuf,
     pub display_path: PathBuf,
     pub is_dir: bool,
     pub children: Vec<Node>,
+    /// Excluded by gitignore but kept because of `--include-gitignored`
+    pub ignored: bool,
+    /// Link target for symlinks kept by `--show-symlinks` (never followed)
+    pub symlink_target: Option<PathBuf>,
+    /// Regular file with an execute bit set (unix only)
+    pub executable: bool,
+    /// File size in bytes as seen during the walk (0 for directories)
+    pub size: u64,
+    /// File count of a directory collapsed by `--prune-dirs-over` (immediate
+    /// files) or `--summarize-known-dirs` (all files below it)
+    pub collapsed_files: Option<usize>,
 }
 
 impl Node {
@@ -18,6 +29,11 @@ impl Node {
             display_path,
             is_dir,
             children: Vec::new(),
+            ignored: false,
+            symlink_target: None,
+            executable: false,
+            size: 0,
+            collapsed_files: None,
         }
     }
 
@@ -25,4 +41,98 @@ impl Node {
         self.display_path = display_path;
         self
     }
+
+    pub fn with_ignored(mut self, ignored: bool) -> Self {
+        self.ignored = ignored;
+        self
+    }
+
+    pub fn with_executable(mut self, executable: bool) -> Self {
+        self.executable = executable;
+        self
+    }
+
+    pub fn with_symlink_target(mut self, target: Option<PathBuf>) -> Self {
+        self.symlink_target = target;
+        self
+    }
+
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_collapsed_files(mut self, count: Option<usize>) -> Self {
+        self.collapsed_files = count;
+        self
+    }
+
+    /// Total size in bytes of all files in this subtree
+    #[allow(dead_code)] // Library API
+    pub fn total_size(&self) -> u64 {
+        if self.is_dir {
+            self.children.iter().map(Node::total_size).sum()
+        } else {
+            self.size
+        }
+    }
+
+    /// Number of files in this subtree (a file counts itself)
+    pub fn file_count(&self) -> usize {
+        if self.is_dir {
+            self.children.iter().map(Node::file_count).sum()
+        } else {
+            1
+        }
+    }
+
+    /// Number of directories below this node (not counting itself)
+    #[allow(dead_code)] // Library API
+    pub fn dir_count(&self) -> usize {
+        self.children
+            .iter()
+            .filter(|c| c.is_dir)
+            .map(|c| 1 + c.dir_count())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, size: u64) -> Node {
+        Node::new(name.to_string(), PathBuf::from(name), false).with_size(size)
+    }
+
+    fn dir(name: &str, children: Vec<Node>) -> Node {
+        let mut node = Node::new(name.to_string(), PathBuf::from(name), true);
+        node.children = children;
+        node
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let tree = dir(
+            "root",
+            vec![
+                dir("src", vec![file("main.rs", 100), file("lib.rs", 50)]),
+                dir("empty", vec![]),
+                file("README.md", 10),
+            ],
+        );
+
+        assert_eq!(tree.total_size(), 160);
+        assert_eq!(tree.file_count(), 3);
+        assert_eq!(tree.dir_count(), 2);
+
+        let src = &tree.children[0];
+        assert_eq!(src.total_size(), 150);
+        assert_eq!(src.file_count(), 2);
+        assert_eq!(src.dir_count(), 0);
+
+        let leaf = file("a.txt", 7);
+        assert_eq!(leaf.total_size(), 7);
+        assert_eq!(leaf.file_count(), 1);
+    }
 }
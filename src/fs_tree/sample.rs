@@ -0,0 +1,115 @@
+use super::node::Node;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Keep a reproducible pseudo-random subset of `n` files for `--sample`.
+///
+/// Each file is ranked by a hash of the seed and its display path, and the
+/// `n` lowest ranks survive, so the same seed picks the same files on every
+/// run and machine. Directories that lose all their files are dropped;
+/// ancestors of kept files stay.
+pub fn sample_files(root: &mut Node, n: usize, seed: u64) {
+    let mut ranked: Vec<(u64, PathBuf)> = Vec::new();
+    collect_ranked(root, seed, &mut ranked);
+    if ranked.len() <= n {
+        return;
+    }
+
+    ranked.sort();
+    let kept: HashSet<PathBuf> = ranked.into_iter().take(n).map(|(_, p)| p).collect();
+    retain_sampled(root, &kept);
+}
+
+fn collect_ranked(node: &Node, seed: u64, out: &mut Vec<(u64, PathBuf)>) {
+    for child in &node.children {
+        if child.is_dir {
+            collect_ranked(child, seed, out);
+        } else {
+            let path = child.display_path.clone();
+            out.push((rank(seed, &path.to_string_lossy()), path));
+        }
+    }
+}
+
+/// Drop unsampled files; returns whether `node` still holds any file
+fn retain_sampled(node: &mut Node, kept: &HashSet<PathBuf>) -> bool {
+    node.children.retain_mut(|child| {
+        if !child.is_dir {
+            return kept.contains(&child.display_path);
+        }
+        let had_files = child.file_count() > 0;
+        retain_sampled(child, kept) || !had_files
+    });
+    node.file_count() > 0
+}
+
+/// 64-bit FNV-1a over the seed and path. Stable across platforms and Rust
+/// versions, unlike `DefaultHasher`.
+fn rank(seed: u64, path: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in seed.to_le_bytes().iter().chain(path.as_bytes()) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(files: &[&str]) -> Node {
+        let mut root = Node::new(".".to_string(), PathBuf::from("."), true);
+        let mut src = Node::new("src".to_string(), PathBuf::from("src"), true)
+            .with_display_path(PathBuf::from("src"));
+        for name in files {
+            let path = PathBuf::from(format!("src/{}", name));
+            src.children
+                .push(Node::new(name.to_string(), path.clone(), false).with_display_path(path));
+        }
+        root.children.push(src);
+        root
+    }
+
+    fn names(node: &Node) -> Vec<String> {
+        node.children[0]
+            .children
+            .iter()
+            .map(|c| c.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_sample_is_deterministic() {
+        let files = ["a.rs", "b.rs", "c.rs", "d.rs", "e.rs", "f.rs"];
+
+        let mut first = tree(&files);
+        sample_files(&mut first, 3, 42);
+        let mut second = tree(&files);
+        sample_files(&mut second, 3, 42);
+
+        assert_eq!(first.file_count(), 3);
+        assert_eq!(names(&first), names(&second));
+    }
+
+    #[test]
+    fn test_sample_keeps_everything_when_small() {
+        let mut root = tree(&["a.rs", "b.rs"]);
+        sample_files(&mut root, 5, 0);
+        assert_eq!(root.file_count(), 2);
+    }
+
+    #[test]
+    fn test_sample_drops_emptied_dirs() {
+        let mut root = tree(&["a.rs", "b.rs"]);
+        root.children.push(
+            Node::new("docs".to_string(), PathBuf::from("docs"), true)
+                .with_display_path(PathBuf::from("docs")),
+        );
+        sample_files(&mut root, 0, 0);
+
+        // src/ lost its files; the already-empty docs/ is left alone
+        assert_eq!(root.children.len(), 1);
+        assert_eq!(root.children[0].name, "docs");
+    }
+}
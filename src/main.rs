@@ -10,11 +10,12 @@ mod safety;
 mod terminal;
 mod util;
 
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
 use cli::Args;
-use fs_tree::{build_tree, ProgressTracker};
+use fs_tree::{build_multi_tree, build_tree, ProgressTracker};
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use terminal::animation::AnimationRunner;
 use terminal::capabilities::TerminalCapabilities;
 use terminal::detect::TerminalDetector;
@@ -36,17 +37,37 @@ fn main() -> io::Result<()> {
     // Restore default SIGPIPE behavior so piping to head/less doesn't panic
     reset_sigpipe();
 
-    let args = Args::parse();
+    let mut args = Args::parse();
+    args.check_deprecated();
+    args.apply_deterministic();
+    if let Err(msg) = args.validate() {
+        Args::command()
+            .error(ErrorKind::ArgumentConflict, msg)
+            .exit();
+    }
+    args.load_annotations();
+
+    let target = args.target_dir();
 
     // Determine display root
-    let display_root = Path::new(&args.target)
+    let display_root = Path::new(target)
         .canonicalize()
-        .unwrap_or_else(|_| std::path::PathBuf::from(&args.target));
+        .unwrap_or_else(|_| std::path::PathBuf::from(target));
 
     // Get the root path for pattern matching
-    let root_path = Path::new(&args.target)
+    let root_path = Path::new(target)
         .canonicalize()
-        .unwrap_or_else(|_| Path::new(&args.target).to_path_buf());
+        .unwrap_or_else(|_| Path::new(target).to_path_buf());
+
+    // Trace the filtering decision for one path instead of rendering
+    if let Some(path) = &args.explain {
+        let spec = matcher::MatchSpec::from_args(&args, Path::new(target));
+        let engine = matcher::MatcherEngine::compile(&spec, &root_path)?;
+        for line in matcher::explain::explain(&engine, &root_path, Path::new(path)) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
 
     // Set up progress tracking and animation
     let detector = TerminalDetector::new();
@@ -62,16 +83,66 @@ fn main() -> io::Result<()> {
     let mut animation_runner = AnimationRunner::new(show_animation, progress_tracker.clone());
 
     // Build tree using unified WalkBuilder approach
-    let root_node = build_tree(&args.target, &args, &root_path, &display_root)?;
+    let mut root_node = if args.target.len() > 1 {
+        build_multi_tree(&args.target, &args)?
+    } else {
+        build_tree(target, &args, &root_path, &display_root)?
+    };
+    // Renaming the root is the only place --rename-root is read: its name
+    // reaches the IR, and its display path labels the root line
+    if let Some(name) = &args.rename_root {
+        root_node.name = name.clone();
+        root_node.display_path = PathBuf::from(name);
+    }
 
     // Stop animation once tree is built
     animation_runner.complete();
 
+    // Gates on the final stats, after the IR filters (--min-loc, ...) ran
+    if args.fail_on_empty || !args.require.is_empty() {
+        let stats = render::pipeline::collect_stats(&root_node, &args);
+
+        // Treat "nothing left after filtering" as a failure when asked to
+        if args.fail_on_empty && stats.total_files() == 0 {
+            eprintln!("tree2md: no files to show after filtering");
+            std::process::exit(1);
+        }
+
+        let failures: Vec<String> = args
+            .require
+            .iter()
+            .filter_map(|req| req.check(&stats).err())
+            .collect();
+        if !failures.is_empty() {
+            for failure in &failures {
+                eprintln!("tree2md: requirement {}", failure);
+            }
+            std::process::exit(1);
+        }
+    }
+
+    // Split mode writes files instead of printing a single tree
+    if let Some(out_dir) = &args.split_output {
+        output::split::write_split(&root_node, &args, Path::new(out_dir))?;
+        return Ok(());
+    }
+
     // Create terminal capabilities and renderer
     let capabilities = TerminalCapabilities::new();
     let mut renderer = render::create_renderer(&args, &capabilities);
     let output = renderer.render_tree(&root_node);
 
+    // Verify against a snapshot instead of printing
+    if let Some(snapshot) = &args.check {
+        let expected = std::fs::read_to_string(snapshot)?;
+        if expected != output {
+            eprintln!("tree2md: output differs from {}", snapshot);
+            eprint!("{}", util::diff::line_diff(&expected, &output));
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Print to stdout
     print!("{}", output);
 
@@ -103,7 +174,7 @@ mod tests {
         fs::write(temp_path.join("src/main.rs"), "fn main() {}").unwrap();
         fs::write(temp_path.join("README.md"), "# Test").unwrap();
 
-        let args = Args::parse_from(&["tree2md", temp_path.to_str().unwrap()]);
+        let args = Args::parse_from(["tree2md", temp_path.to_str().unwrap()]);
         let display_root = temp_path.to_path_buf();
         let tree =
             build_tree(temp_path.to_str().unwrap(), &args, temp_path, &display_root).unwrap();
@@ -1,11 +1,14 @@
-use super::{MatchSpec, RelPath};
+use super::{MatchSpec, MtimeRange, RelPath};
+use crate::profile::FileType;
 use crate::safety::SafetyPreset;
-use globset::{Glob, GlobSet, GlobSetBuilder};
+use crate::util::format::format_size;
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use std::collections::HashSet;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// Selection decision for a path
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +19,42 @@ pub enum Selection {
     Exclude,
     /// Prune this directory (don't descend into it)
     PruneDir,
+    /// Gitignored, but kept and marked (`--include-gitignored`).
+    /// Directories are shown without descending into them.
+    Ignored,
+    /// Keep this directory as a leaf, without descending into it
+    Shallow,
+}
+
+/// Rule that produced a `Selection`, reported by `--explain`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    /// `.git` directories are always pruned
+    GitDir,
+    /// Include patterns exist but none matched
+    NotIncluded,
+    /// Matched an include pattern (-I)
+    Include,
+    /// Directory may hold files matching an include pattern
+    MayContainIncludes,
+    /// Matched an exclude pattern (-X)
+    Exclude,
+    /// Matched a gitignore rule
+    Gitignore,
+    /// Matched a safety preset rule
+    Safety,
+    /// Matched a rule in a `.ignore` file (`--respect-ignore`) or `--extra-ignore` file
+    IgnoreFile,
+    /// Git submodule kept as a leaf by `--exclude-submodules`
+    Submodule,
+    /// Hidden directory pruned by `--exclude-dot-dirs`
+    DotDir,
+    /// Lockfile dropped by `--hide-lockfiles`
+    Lockfile,
+    /// File over the `--exclude-larger-than` limit
+    TooLarge,
+    /// No rule matched
+    Default,
 }
 
 /// Compiled matcher engine that evaluates paths against rules
@@ -29,6 +68,9 @@ pub struct MatcherEngine {
     /// Compiled include glob patterns
     include_globset: Option<GlobSet>,
 
+    /// Original exclude glob patterns (for explaining decisions)
+    exclude_glob: Vec<String>,
+
     /// Compiled exclude glob patterns
     exclude_globset: Option<GlobSet>,
 
@@ -37,6 +79,13 @@ pub struct MatcherEngine {
     /// A scope of "" means root-level (applies to everything).
     gitignore_layers: Vec<(String, Gitignore)>,
 
+    /// `.ignore` rules (ripgrep/fd convention), scoped like `gitignore_layers`,
+    /// followed by root-scoped `--extra-ignore` files
+    ignore_file_layers: Vec<(String, Gitignore)>,
+
+    /// Report gitignore matches as `Selection::Ignored` instead of excluding
+    mark_gitignored: bool,
+
     /// Safety preset for excluding sensitive files
     safety_preset: Option<SafetyPreset>,
 
@@ -45,6 +94,21 @@ pub struct MatcherEngine {
 
     /// Whether matching is case sensitive
     case_sensitive: bool,
+
+    /// Prune directories whose name starts with `.`
+    exclude_dot_dirs: bool,
+
+    /// Directories shown without their contents (e.g. submodules)
+    shallow_dirs: HashSet<String>,
+
+    /// Allowed file modification times (`--modified-since`/`--older-than`)
+    mtime_range: Option<MtimeRange>,
+
+    /// Exclude lockfiles (`--hide-lockfiles`)
+    hide_lockfiles: bool,
+
+    /// Exclude files larger than this many bytes (`--exclude-larger-than`)
+    max_file_size: Option<u64>,
 }
 
 impl MatcherEngine {
@@ -67,7 +131,7 @@ impl MatcherEngine {
         let include_globset = if !spec.include_glob.is_empty() {
             let mut builder = GlobSetBuilder::new();
             for pattern in &spec.include_glob {
-                let glob = Glob::new(pattern).map_err(|e| {
+                let glob = build_glob(pattern, spec.case_sensitive).map_err(|e| {
                     io::Error::new(
                         io::ErrorKind::InvalidInput,
                         format!("Invalid include glob pattern '{}': {}", pattern, e),
@@ -89,7 +153,7 @@ impl MatcherEngine {
         let exclude_globset = if !spec.exclude_glob.is_empty() {
             let mut builder = GlobSetBuilder::new();
             for pattern in &spec.exclude_glob {
-                let glob = Glob::new(pattern).map_err(|e| {
+                let glob = build_glob(pattern, spec.case_sensitive).map_err(|e| {
                     io::Error::new(
                         io::ErrorKind::InvalidInput,
                         format!("Invalid exclude glob pattern '{}': {}", pattern, e),
@@ -120,7 +184,8 @@ impl MatcherEngine {
             let mut root_builder = GitignoreBuilder::new(root);
             let mut has_root_patterns = false;
 
-            // Walk upward from root to collect ancestor .gitignore files
+            // Walk upward from root to collect ancestor .gitignore files,
+            // unless --ignore-parent-gitignore keeps it to root/.gitignore
             let mut current = root;
             loop {
                 let gitignore_path = current.join(".gitignore");
@@ -128,6 +193,9 @@ impl MatcherEngine {
                     root_builder.add(gitignore_path);
                     has_root_patterns = true;
                 }
+                if !spec.parent_gitignores {
+                    break;
+                }
                 if let Some(parent) = current.parent() {
                     current = parent;
                 } else {
@@ -166,7 +234,7 @@ impl MatcherEngine {
             }
 
             // Nested layers: each subdirectory .gitignore gets its own Gitignore
-            for gitignore_path in Self::collect_nested_gitignores(root) {
+            for gitignore_path in Self::collect_nested_ignore_files(root, ".gitignore") {
                 let dir = gitignore_path.parent().unwrap();
                 let scope = dir
                     .strip_prefix(root)
@@ -190,6 +258,51 @@ impl MatcherEngine {
             Vec::new()
         };
 
+        // Build .ignore layers the same way: one layer per file, scoped to its dir
+        let mut ignore_file_layers: Vec<(String, Gitignore)> = Vec::new();
+        if spec.respect_ignore_files {
+            let mut ignore_paths = Self::collect_nested_ignore_files(root, ".ignore");
+            if root.join(".ignore").is_file() {
+                ignore_paths.insert(0, root.join(".ignore"));
+            }
+            for ignore_path in ignore_paths {
+                let dir = ignore_path.parent().unwrap();
+                let scope = dir
+                    .strip_prefix(root)
+                    .unwrap_or(Path::new(""))
+                    .to_string_lossy()
+                    .replace('\\', "/");
+
+                let mut builder = GitignoreBuilder::new(dir);
+                builder.add(&ignore_path);
+                let gi = builder.build().map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("Failed to build .ignore rules for {}: {}", scope, e),
+                    )
+                })?;
+                ignore_file_layers.push((scope, gi));
+            }
+        }
+
+        // --extra-ignore files: gitignore syntax, anchored at the root
+        for extra_path in &spec.extra_ignore_files {
+            let mut builder = GitignoreBuilder::new(root);
+            if let Some(e) = builder.add(extra_path) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Failed to read {}: {}", extra_path.display(), e),
+                ));
+            }
+            let gi = builder.build().map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Failed to build rules from {}: {}", extra_path.display(), e),
+                )
+            })?;
+            ignore_file_layers.push((String::new(), gi));
+        }
+
         // Create safety preset if enabled
         let safety_preset = if spec.use_safety_preset {
             Some(SafetyPreset::new())
@@ -200,15 +313,40 @@ impl MatcherEngine {
         Ok(Self {
             include_ext_set,
             include_glob: spec.include_glob.clone(),
+            exclude_glob: spec.exclude_glob.clone(),
             include_globset,
             exclude_globset,
             gitignore_layers,
+            ignore_file_layers,
+            mark_gitignored: spec.mark_gitignored,
             safety_preset,
             has_includes: spec.has_includes(),
             case_sensitive: spec.case_sensitive,
+            exclude_dot_dirs: spec.exclude_dot_dirs,
+            shallow_dirs: spec.shallow_dirs.iter().cloned().collect(),
+            mtime_range: spec.mtime_range,
+            hide_lockfiles: spec.hide_lockfiles,
+            max_file_size: spec.max_file_size,
         })
     }
 
+    /// Whether a file's modification time falls inside the configured window.
+    /// Path rules come first; this runs on files that survived them.
+    pub fn mtime_allowed(&self, modified: SystemTime) -> bool {
+        self.mtime_range
+            .is_none_or(|range| range.contains(modified))
+    }
+
+    /// Whether a directory is listed without being walked (`--exclude-submodules`)
+    pub fn is_shallow_dir(&self, rel_path: &RelPath) -> bool {
+        self.shallow_dirs.contains(rel_path.as_match_str().as_ref())
+    }
+
+    /// Whether an mtime window is active
+    pub fn has_mtime_range(&self) -> bool {
+        self.mtime_range.is_some()
+    }
+
     /// Select whether to include, exclude, or prune a file
     ///
     /// Priority order:
@@ -217,136 +355,250 @@ impl MatcherEngine {
     ///    (path-specific includes explicitly target files and override exclude)
     /// 3. If file matches exclude → Exclude (narrows generic includes like `**/*.rs`)
     /// 4. If file matched a generic include → Include (overrides gitignore and safety)
-    /// 5. If gitignore matches → Exclude
-    /// 6. If safety matches → Exclude
-    /// 7. Default → Include
+    /// 5. If `--hide-lockfiles` and the file is a lockfile → Exclude
+    /// 6. If gitignore matches → Exclude (or Ignored with `--include-gitignored`)
+    ///    then `.ignore` rules with `--respect-ignore` → Exclude
+    /// 7. If safety matches → Exclude
+    /// 8. Default → Include
+    #[allow(dead_code)] // Used in tests
     pub fn select_file(&self, rel_path: &RelPath) -> Selection {
+        self.decide_file(rel_path).0
+    }
+
+    /// Like `select_file`, but also reports which rule made the decision
+    pub fn decide_file(&self, rel_path: &RelPath) -> (Selection, Reason) {
         let path_str = rel_path.as_match_str();
 
         let matched_include = self.matches_include_rules(&path_str, rel_path);
 
         // Priority 1: If include patterns exist but file doesn't match any, exclude
         if self.has_includes && !matched_include {
-            return Selection::Exclude;
+            return (Selection::Exclude, Reason::NotIncluded);
         }
 
         // Priority 2: Path-specific includes override exclude
         // (e.g., `-I vendor/**/*.py` overrides `-X vendor`)
         if matched_include && self.matches_path_specific_include(&path_str) {
-            return Selection::Include;
+            return (Selection::Include, Reason::Include);
         }
 
         // Priority 3: Exclude patterns narrow down generic includes
         if let Some(ref exclude_globset) = self.exclude_globset {
             if exclude_globset.is_match(path_str.as_ref()) {
-                return Selection::Exclude;
+                return (Selection::Exclude, Reason::Exclude);
             }
         }
 
         // Priority 4: Generic include overrides gitignore and safety
         if matched_include {
-            return Selection::Include;
+            return (Selection::Include, Reason::Include);
         }
 
-        // Priority 5: Gitignore rules (check each scoped layer)
+        // Priority 5: Lockfiles (--hide-lockfiles)
+        if self.hide_lockfiles
+            && FileType::classify_path(Path::new(path_str.as_ref())) == FileType::Lock
+        {
+            return (Selection::Exclude, Reason::Lockfile);
+        }
+
+        // Priority 6: Gitignore rules (check each scoped layer)
         if self.matches_gitignore(&path_str, rel_path, false) {
-            return Selection::Exclude;
+            return (self.gitignored(Selection::Exclude), Reason::Gitignore);
+        }
+        if layer_rule(&self.ignore_file_layers, &path_str, rel_path, false).is_some() {
+            return (Selection::Exclude, Reason::IgnoreFile);
         }
 
-        // Priority 6: Safety preset
+        // Priority 7: Safety preset
         if let Some(ref safety) = self.safety_preset {
             if safety.matches(path_str.as_ref()) {
-                return Selection::Exclude;
+                return (Selection::Exclude, Reason::Safety);
             }
         }
 
         // Default: include if no rules matched
-        Selection::Include
+        (Selection::Include, Reason::Default)
+    }
+
+    /// `decide_file` for a file of `size` bytes: one that would be shown is
+    /// excluded when over `--exclude-larger-than`, unless an include pattern
+    /// asked for it
+    pub fn decide_file_with_size(&self, rel_path: &RelPath, size: u64) -> (Selection, Reason) {
+        let decision = self.decide_file(rel_path);
+        let too_large = self.max_file_size.is_some_and(|limit| size > limit);
+        if too_large && decision == (Selection::Include, Reason::Default) {
+            (Selection::Exclude, Reason::TooLarge)
+        } else {
+            decision
+        }
     }
 
     /// Select whether to include, exclude, or prune a directory
     ///
     /// Priority order:
     /// 1. .git → always prune
-    /// 2. Gitignore → always prune (like rg/fd: gitignored dirs are never traversed)
-    /// 3. Safety preset → always prune
-    /// 4. Include patterns may keep dir alive (prevents -X from pruning)
-    /// 5. Exclude patterns (-X) → prune
-    /// 6. Default → include
+    /// 2. Submodules (`--exclude-submodules`) → kept as a leaf
+    /// 3. Path-specific includes keep the dir alive
+    /// 4. `--exclude-dot-dirs` → prune hidden dirs
+    /// 5. Gitignore → always prune (like rg/fd: gitignored dirs are never traversed;
+    ///    with `--include-gitignored` the dir itself is kept and marked),
+    ///    then `.ignore` rules with `--respect-ignore`
+    /// 6. Safety preset → always prune
+    /// 7. Include patterns may keep dir alive (prevents -X from pruning)
+    /// 8. Exclude patterns (-X) → prune
+    /// 9. Default → include
+    #[allow(dead_code)] // Used in tests
     pub fn select_dir(&self, rel_path: &RelPath) -> Selection {
+        self.decide_dir(rel_path).0
+    }
+
+    /// Like `select_dir`, but also reports which rule made the decision
+    pub fn decide_dir(&self, rel_path: &RelPath) -> (Selection, Reason) {
         let path_str = rel_path.as_match_str();
 
         // Priority 1: Always exclude .git directory
         if path_str == ".git" || path_str.starts_with(".git/") {
-            return Selection::PruneDir;
+            return (Selection::PruneDir, Reason::GitDir);
         }
 
-        // Priority 2: Path-specific includes override gitignore/safety.
+        // Priority 2: Shallow directories (submodules) are listed, not walked
+        if self.shallow_dirs.contains(path_str.as_ref()) {
+            return (Selection::Shallow, Reason::Submodule);
+        }
+
+        // Priority 3: Path-specific includes override gitignore/safety.
         // e.g., `-I vendor/**/*.py` explicitly targets vendor/, so we must
         // not prune it even if gitignore or safety would normally do so.
         if self.dir_may_contain_path_specific_includes(&path_str) {
-            return Selection::Include;
+            return (Selection::Include, Reason::MayContainIncludes);
         }
 
-        // Priority 3: Gitignore always prunes directories.
+        // Priority 4: --exclude-dot-dirs prunes hidden directories but
+        // leaves hidden files alone
+        if self.exclude_dot_dirs {
+            let name = path_str.rsplit('/').next().unwrap_or(&path_str);
+            if name.starts_with('.') {
+                return (Selection::PruneDir, Reason::DotDir);
+            }
+        }
+
+        // Priority 5: Gitignore always prunes directories.
         // Like rg/fd, gitignored directories are never traversed regardless
         // of generic include patterns. Users can opt out with --use-gitignore=never.
         if self.matches_gitignore(&path_str, rel_path, true) {
-            return Selection::PruneDir;
+            return (self.gitignored(Selection::PruneDir), Reason::Gitignore);
+        }
+        if layer_rule(&self.ignore_file_layers, &path_str, rel_path, true).is_some() {
+            return (Selection::PruneDir, Reason::IgnoreFile);
         }
 
-        // Priority 4: Safety preset always prunes directories.
+        // Priority 6: Safety preset always prunes directories.
         // Users can opt out with --unsafe.
         if let Some(ref safety) = self.safety_preset {
             if safety.matches(path_str.as_ref()) || safety.matches(&format!("{}/", path_str)) {
-                return Selection::PruneDir;
+                return (Selection::PruneDir, Reason::Safety);
             }
         }
 
-        // Priority 5: Check if this directory might contain files matching
+        // Priority 7: Check if this directory might contain files matching
         // any include patterns (including generic ones like `**/src/**`).
         // This prevents `-X` from pruning directories that might have matches.
-        let may_contain_includes = self.dir_may_contain_includes(&path_str);
-        if self.matches_include_rules(&path_str, rel_path) || may_contain_includes {
-            return Selection::Include;
+        if self.matches_include_rules(&path_str, rel_path) {
+            return (Selection::Include, Reason::Include);
+        }
+        if self.dir_may_contain_includes(&path_str) {
+            return (Selection::Include, Reason::MayContainIncludes);
         }
 
-        // Priority 6: Exclude patterns (-X)
+        // Priority 8: Exclude patterns (-X)
         if let Some(ref exclude_globset) = self.exclude_globset {
             // For directory matching, try both with and without trailing slash
             if exclude_globset.is_match(path_str.as_ref())
                 || exclude_globset.is_match(format!("{}/", path_str))
             {
-                return Selection::PruneDir;
+                return (Selection::PruneDir, Reason::Exclude);
             }
         }
 
         // Default: don't prune directories - we need to check their contents
-        Selection::Include
+        (Selection::Include, Reason::Default)
+    }
+
+    /// Human-readable account of a decision, naming the rule that matched
+    pub fn describe(&self, reason: Reason, rel_path: &RelPath, is_dir: bool) -> String {
+        let path_str = rel_path.as_match_str();
+        let path_str = path_str.as_ref();
+        let first_match = |patterns: &[String]| {
+            patterns
+                .iter()
+                .find(|p| {
+                    build_glob(p, self.case_sensitive).is_ok_and(|g| {
+                        let m = g.compile_matcher();
+                        m.is_match(path_str) || (is_dir && m.is_match(format!("{}/", path_str)))
+                    })
+                })
+                .cloned()
+        };
+
+        match reason {
+            Reason::GitDir => "'.git' is always pruned".to_string(),
+            Reason::NotIncluded => "matched no include pattern".to_string(),
+            Reason::Include => match first_match(&self.include_glob) {
+                Some(p) => format!("matched include glob '{}'", p),
+                None => "matched an include extension".to_string(),
+            },
+            Reason::MayContainIncludes => "may contain files matching include patterns".to_string(),
+            Reason::Exclude => match first_match(&self.exclude_glob) {
+                Some(p) => format!("matched exclude glob '{}'", p),
+                None => "matched an exclude pattern".to_string(),
+            },
+            Reason::Gitignore => {
+                let rule = self
+                    .gitignore_rule(path_str, rel_path, is_dir)
+                    .unwrap_or("");
+                format!("matched gitignore rule '{}'", rule)
+            }
+            Reason::Safety => {
+                let rule = self.safety_preset.as_ref().and_then(|s| {
+                    s.matching_rule(path_str)
+                        .or_else(|| s.matching_rule(&format!("{}/", path_str)))
+                });
+                format!("matched safety rule '{}'", rule.unwrap_or(""))
+            }
+            Reason::IgnoreFile => {
+                let rule = layer_rule(&self.ignore_file_layers, path_str, rel_path, is_dir);
+                format!("matched ignore file rule '{}'", rule.unwrap_or(""))
+            }
+            Reason::Submodule => "git submodule (--exclude-submodules)".to_string(),
+            Reason::DotDir => "hidden directory (--exclude-dot-dirs)".to_string(),
+            Reason::Lockfile => "lockfile (--hide-lockfiles)".to_string(),
+            Reason::TooLarge => format!(
+                "larger than {} (--exclude-larger-than)",
+                format_size(self.max_file_size.unwrap_or(0))
+            ),
+            Reason::Default => "no rule matched".to_string(),
+        }
+    }
+
+    /// Decision for a gitignored path: marked when `--include-gitignored` is set
+    fn gitignored(&self, fallback: Selection) -> Selection {
+        if self.mark_gitignored {
+            Selection::Ignored
+        } else {
+            fallback
+        }
     }
 
     /// Check if a path matches any gitignore layer, respecting directory scoping.
     /// Each layer has a scope (relative dir prefix). A layer only applies to
     /// paths under its scope. Scope "" means root (applies to everything).
     fn matches_gitignore(&self, path_str: &str, rel_path: &RelPath, is_dir: bool) -> bool {
-        for (scope, gitignore) in &self.gitignore_layers {
-            // Check if path is under this layer's scope
-            if !scope.is_empty() && !path_str.starts_with(&format!("{}/", scope)) {
-                continue;
-            }
-
-            // For scoped layers, match against the path relative to the scope dir
-            let match_path = if scope.is_empty() {
-                rel_path.to_path_buf()
-            } else {
-                PathBuf::from(&path_str[scope.len() + 1..])
-            };
+        self.gitignore_rule(path_str, rel_path, is_dir).is_some()
+    }
 
-            if gitignore.matched(&match_path, is_dir).is_ignore() {
-                return true;
-            }
-        }
-        false
+    /// The gitignore rule that ignores a path, if any
+    fn gitignore_rule(&self, path_str: &str, rel_path: &RelPath, is_dir: bool) -> Option<&str> {
+        layer_rule(&self.gitignore_layers, path_str, rel_path, is_dir)
     }
 
     /// Check if a path matches any include rules
@@ -393,7 +645,7 @@ impl MatcherEngine {
             for pattern in &self.include_glob {
                 if !pattern.starts_with("**/") {
                     // Build a single glob to test this specific pattern
-                    if let Ok(glob) = Glob::new(pattern) {
+                    if let Ok(glob) = build_glob(pattern, self.case_sensitive) {
                         if glob.compile_matcher().is_match(path_str) {
                             return true;
                         }
@@ -404,9 +656,10 @@ impl MatcherEngine {
         false
     }
 
-    /// Recursively collect `.gitignore` files from subdirectories of root.
-    /// The root's own `.gitignore` is excluded (already handled by the upward walk).
-    fn collect_nested_gitignores(root: &Path) -> Vec<PathBuf> {
+    /// Recursively collect ignore files named `file_name` (e.g. `.gitignore`)
+    /// from subdirectories of root. The root's own file is excluded: the
+    /// caller handles it (for `.gitignore`, via the upward walk).
+    fn collect_nested_ignore_files(root: &Path, file_name: &str) -> Vec<PathBuf> {
         let mut result = Vec::new();
         let mut stack = Vec::new();
 
@@ -424,9 +677,9 @@ impl MatcherEngine {
         }
 
         while let Some(dir) = stack.pop() {
-            let gitignore_path = dir.join(".gitignore");
-            if gitignore_path.exists() {
-                result.push(gitignore_path);
+            let ignore_path = dir.join(file_name);
+            if ignore_path.exists() {
+                result.push(ignore_path);
             }
 
             if let Ok(entries) = std::fs::read_dir(&dir) {
@@ -523,11 +776,89 @@ impl MatcherEngine {
     }
 }
 
+/// The first rule in scoped ignore layers that ignores a path, if any.
+/// A layer only applies to paths under its scope; scope "" applies to everything.
+fn layer_rule<'a>(
+    layers: &'a [(String, Gitignore)],
+    path_str: &str,
+    rel_path: &RelPath,
+    is_dir: bool,
+) -> Option<&'a str> {
+    for (scope, gitignore) in layers {
+        // Check if path is under this layer's scope
+        if !scope.is_empty() && !path_str.starts_with(&format!("{}/", scope)) {
+            continue;
+        }
+
+        // For scoped layers, match against the path relative to the scope dir
+        let match_path = if scope.is_empty() {
+            rel_path.to_path_buf()
+        } else {
+            PathBuf::from(&path_str[scope.len() + 1..])
+        };
+
+        if let ignore::Match::Ignore(glob) = gitignore.matched(&match_path, is_dir) {
+            return Some(glob.original());
+        }
+    }
+    None
+}
+
+/// Compile a single user glob, folding case unless `case_sensitive`
+fn build_glob(pattern: &str, case_sensitive: bool) -> Result<Glob, globset::Error> {
+    GlobBuilder::new(pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_mtime_allowed() {
+        use std::time::Duration;
+
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let spec = MatchSpec::new().with_mtime_range(MtimeRange {
+            oldest: Some(now - day * 30),
+            newest: Some(now - day),
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let engine = MatcherEngine::compile(&spec, temp_dir.path()).unwrap();
+        assert!(engine.mtime_allowed(now - day * 7));
+        assert!(!engine.mtime_allowed(now));
+        assert!(!engine.mtime_allowed(now - day * 90));
+
+        let open = MatcherEngine::compile(&MatchSpec::new(), temp_dir.path()).unwrap();
+        assert!(open.mtime_allowed(now - day * 90));
+    }
+
+    #[test]
+    fn test_case_insensitive_globs() {
+        let spec = MatchSpec::new()
+            .with_include_glob(vec!["**/*.RS".to_string()])
+            .with_exclude_glob(vec!["**/GEN/**".to_string()])
+            .with_case_sensitive(false);
+
+        let temp_dir = TempDir::new().unwrap();
+        let engine = MatcherEngine::compile(&spec, temp_dir.path()).unwrap();
+
+        let main_rs = RelPath::from_relative("src/main.rs");
+        assert_eq!(engine.select_file(&main_rs), Selection::Include);
+
+        let generated = RelPath::from_relative("gen/out.rs");
+        assert_eq!(engine.select_file(&generated), Selection::Exclude);
+
+        // Case-sensitive matching keeps the old behavior
+        let engine =
+            MatcherEngine::compile(&spec.with_case_sensitive(true), temp_dir.path()).unwrap();
+        assert_eq!(engine.select_file(&main_rs), Selection::Exclude);
+    }
+
     #[test]
     fn test_include_extensions() {
         let spec = MatchSpec::new().with_include_ext(vec![".rs".to_string(), ".go".to_string()]);
@@ -607,6 +938,23 @@ mod tests {
         assert_eq!(engine.select_file(&test_rs), Selection::Exclude);
     }
 
+    #[test]
+    fn test_parent_gitignores_can_be_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("sub")).unwrap();
+        std::fs::write(temp_dir.path().join(".gitignore"), "*.scratch\n").unwrap();
+        let root = temp_dir.path().join("sub");
+        let scratch = RelPath::from_relative("notes.scratch");
+
+        let spec = MatchSpec::new().with_gitignore(true);
+        let engine = MatcherEngine::compile(&spec, &root).unwrap();
+        assert_eq!(engine.select_file(&scratch), Selection::Exclude);
+
+        let spec = spec.with_parent_gitignores(false);
+        let engine = MatcherEngine::compile(&spec, &root).unwrap();
+        assert_eq!(engine.select_file(&scratch), Selection::Include);
+    }
+
     #[test]
     fn test_nested_gitignore_scoping() {
         let temp_dir = TempDir::new().unwrap();
@@ -655,4 +1003,56 @@ mod tests {
         let other_hidden_dir = RelPath::from_relative(".config");
         assert_eq!(engine.select_dir(&other_hidden_dir), Selection::Include);
     }
+
+    #[test]
+    fn test_exclude_dot_dirs() {
+        let spec = MatchSpec::new().with_exclude_dot_dirs(true);
+        let temp_dir = TempDir::new().unwrap();
+        let engine = MatcherEngine::compile(&spec, temp_dir.path()).unwrap();
+
+        let github = RelPath::from_relative(".github");
+        assert_eq!(engine.select_dir(&github), Selection::PruneDir);
+        let nested = RelPath::from_relative("src/.cache");
+        assert_eq!(engine.select_dir(&nested), Selection::PruneDir);
+        let src = RelPath::from_relative("src");
+        assert_eq!(engine.select_dir(&src), Selection::Include);
+
+        // Hidden files are untouched
+        let hidden_file = RelPath::from_relative(".gitignore");
+        assert_eq!(engine.select_file(&hidden_file), Selection::Include);
+    }
+
+    #[test]
+    fn test_hide_lockfiles_and_size_limit() {
+        let spec = MatchSpec::new()
+            .with_hide_lockfiles(true)
+            .with_max_file_size(Some(1024));
+        let temp_dir = TempDir::new().unwrap();
+        let engine = MatcherEngine::compile(&spec, temp_dir.path()).unwrap();
+
+        let lock = RelPath::from_relative("sub/Cargo.lock");
+        assert_eq!(
+            engine.decide_file(&lock),
+            (Selection::Exclude, Reason::Lockfile)
+        );
+        let big = RelPath::from_relative("big.bin");
+        assert_eq!(
+            engine.decide_file_with_size(&big, 4096),
+            (Selection::Exclude, Reason::TooLarge)
+        );
+        assert_eq!(engine.select_file(&big), Selection::Include);
+        assert_eq!(
+            engine.decide_file_with_size(&big, 1024),
+            (Selection::Include, Reason::Default)
+        );
+
+        // An include pattern brings both back
+        let spec = spec.with_include_glob(vec!["Cargo.lock".into(), "*.bin".into()]);
+        let engine = MatcherEngine::compile(&spec, temp_dir.path()).unwrap();
+        assert_eq!(engine.select_file(&lock), Selection::Include);
+        assert_eq!(
+            engine.decide_file_with_size(&big, 4096).0,
+            Selection::Include
+        );
+    }
 }
@@ -0,0 +1,49 @@
+use super::{MatcherEngine, RelPath, Selection};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Decision trace for `--explain`: each ancestor directory from the root down,
+/// stopping at the first one that is not descended into, then the path itself
+pub fn explain(engine: &MatcherEngine, root: &Path, path: &Path) -> Vec<String> {
+    let components: Vec<Component> = path
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect();
+    let mut lines = Vec::new();
+    let mut current = PathBuf::new();
+
+    for (i, component) in components.iter().enumerate() {
+        current.push(component);
+        let is_last = i + 1 == components.len();
+        let is_dir = !is_last || root.join(&current).is_dir();
+
+        let rel_path = RelPath::from_relative(&current);
+        let (selection, reason) = if is_dir {
+            engine.decide_dir(&rel_path)
+        } else {
+            match fs::metadata(root.join(&current)) {
+                Ok(metadata) if metadata.is_file() => {
+                    engine.decide_file_with_size(&rel_path, metadata.len())
+                }
+                _ => engine.decide_file(&rel_path),
+            }
+        };
+        lines.push(format!(
+            "{}{}: {} -> {:?}",
+            rel_path.as_match_str(),
+            if is_dir { "/" } else { "" },
+            engine.describe(reason, &rel_path, is_dir),
+            selection
+        ));
+
+        if !is_last && selection != Selection::Include {
+            lines.push(format!(
+                "{}: not reached, an ancestor directory is not descended into",
+                path.display()
+            ));
+            break;
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,59 @@
+use std::path::Path;
+
+/// Patterns from `<root>/.gitattributes` whose attributes mark them
+/// `linguist-generated` (bare or `=true`). Unset (`-linguist-generated`)
+/// and `=false` entries are ignored, as are comments and macro lines.
+pub fn linguist_generated_patterns(root: &Path) -> Vec<String> {
+    match std::fs::read_to_string(root.join(".gitattributes")) {
+        Ok(content) => parse_linguist_generated(&content),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn parse_linguist_generated(content: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("[attr]") {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let Some(pattern) = fields.next() else {
+            continue;
+        };
+
+        let generated = fields.any(|attr| {
+            attr == "linguist-generated"
+                || attr == "linguist-generated=true"
+                || attr == "linguist-generated=1"
+        });
+        if generated {
+            // Leading "/" anchors to the repository root, which is where
+            // our relative paths start anyway
+            patterns.push(pattern.trim_start_matches('/').to_string());
+        }
+    }
+
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_linguist_generated() {
+        let patterns = parse_linguist_generated(
+            "# generated code\n\
+             *.pb.go linguist-generated\n\
+             /api/schema.json linguist-generated=true text\n\
+             vendor/** -linguist-generated\n\
+             docs/*.md linguist-generated=false\n\
+             *.rs text eol=lf\n\
+             [attr]gen linguist-generated\n",
+        );
+        assert_eq!(patterns, vec!["*.pb.go", "api/schema.json"]);
+    }
+}
@@ -0,0 +1,53 @@
+use std::path::Path;
+
+/// Submodule paths declared in `<root>/.gitmodules` (the `path = ...` keys),
+/// relative to the root with forward slashes
+pub fn submodule_paths(root: &Path) -> Vec<String> {
+    match std::fs::read_to_string(root.join(".gitmodules")) {
+        Ok(content) => parse_submodule_paths(&content),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn parse_submodule_paths(content: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() == "path" {
+            let value = value.trim().trim_matches('"');
+            let value = value.trim_start_matches("./").trim_end_matches('/');
+            if !value.is_empty() {
+                paths.push(value.replace('\\', "/"));
+            }
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_submodule_paths() {
+        let paths = parse_submodule_paths(
+            "[submodule \"vendor/lib\"]\n\
+             \tpath = vendor/lib\n\
+             \turl = https://example.com/lib.git\n\
+             # path = commented/out\n\
+             [submodule \"docs\"]\n\
+             \tpath = \"docs/theme/\"\n\
+             \tbranch = main\n",
+        );
+        assert_eq!(paths, vec!["vendor/lib", "docs/theme"]);
+    }
+}
@@ -1,7 +1,10 @@
 pub mod engine;
+pub mod explain;
+pub mod gitattributes;
+pub mod gitmodules;
 pub mod rel_path;
 pub mod spec;
 
-pub use engine::{MatcherEngine, Selection};
+pub use engine::{MatcherEngine, Reason, Selection};
 pub use rel_path::RelPath;
-pub use spec::MatchSpec;
+pub use spec::{MatchSpec, MtimeRange};
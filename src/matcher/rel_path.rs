@@ -36,7 +36,6 @@ impl RelPath {
     }
 
     /// Create a RelPath directly from a relative path
-    #[allow(dead_code)] // Used in tests
     pub fn from_relative<P: AsRef<Path>>(path: P) -> Self {
         Self {
             inner: path.as_ref().as_os_str().to_owned(),
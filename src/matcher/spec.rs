@@ -1,4 +1,8 @@
+use super::gitattributes::linguist_generated_patterns;
+use super::gitmodules::submodule_paths;
 use crate::cli::Args;
+use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// Declarative specification of file matching rules
 #[derive(Debug, Clone)]
@@ -15,16 +19,60 @@ pub struct MatchSpec {
     /// Whether to respect gitignore files
     pub respect_gitignore: bool,
 
+    /// Whether .gitignore files above the root apply too
+    pub parent_gitignores: bool,
+
+    /// Whether to respect `.ignore` files (ripgrep/fd convention)
+    pub respect_ignore_files: bool,
+
+    /// Files in gitignore syntax applied from the root (`--extra-ignore`)
+    pub extra_ignore_files: Vec<PathBuf>,
+
+    /// Keep gitignored paths and mark them instead of dropping them
+    pub mark_gitignored: bool,
+
     /// Whether to apply safety presets (exclude sensitive files)
     pub use_safety_preset: bool,
 
     /// Whether pattern matching is case sensitive
     pub case_sensitive: bool,
 
+    /// Prune directories whose name starts with `.` (hidden files stay)
+    pub exclude_dot_dirs: bool,
+
+    /// Directories to show without descending into them (relative paths)
+    pub shallow_dirs: Vec<String>,
+
+    /// Allowed file modification times (`--modified-since`/`--older-than`)
+    pub mtime_range: Option<MtimeRange>,
+
+    /// Exclude lockfiles (`--hide-lockfiles`)
+    pub hide_lockfiles: bool,
+
+    /// Exclude files larger than this many bytes (`--exclude-larger-than`)
+    pub max_file_size: Option<u64>,
+
     /// Keep directories until pruned (usually true to allow tree building)
     pub _keep_dirs_until_pruned: bool,
 }
 
+/// Inclusive modification-time window; a missing bound is open
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MtimeRange {
+    /// Files modified before this are dropped (`--modified-since`)
+    pub oldest: Option<SystemTime>,
+    /// Files modified after this are dropped (`--older-than`)
+    pub newest: Option<SystemTime>,
+}
+
+impl MtimeRange {
+    /// Whether `modified` falls inside the window
+    pub fn contains(&self, modified: SystemTime) -> bool {
+        self.oldest.is_none_or(|oldest| oldest <= modified)
+            && self.newest.is_none_or(|newest| modified <= newest)
+    }
+}
+
 impl Default for MatchSpec {
     fn default() -> Self {
         Self {
@@ -32,8 +80,17 @@ impl Default for MatchSpec {
             include_glob: Vec::new(),
             exclude_glob: Vec::new(),
             respect_gitignore: false,
+            parent_gitignores: true,
+            respect_ignore_files: false,
+            extra_ignore_files: Vec::new(),
+            mark_gitignored: false,
             use_safety_preset: true, // Default to safe mode ON
             case_sensitive: true,
+            exclude_dot_dirs: false,
+            shallow_dirs: Vec::new(),
+            mtime_range: None,
+            hide_lockfiles: false,
+            max_file_size: None,
             _keep_dirs_until_pruned: true,
         }
     }
@@ -49,7 +106,7 @@ impl MatchSpec {
     /// Normalize a glob pattern to be recursive if it doesn't contain path separators
     /// For example: "*.rs" becomes "**/*.rs" to match files at any depth
     /// For directory names like "specs", it becomes "**/{name}/**" to match at any depth (like .gitignore)
-    fn normalize_pattern(pattern: &str) -> String {
+    pub(crate) fn normalize_pattern(pattern: &str) -> String {
         // Trailing "/" just means "this is a directory" — strip it before normalization
         // so that "hoge/" and "hoge" behave identically
         let pattern = pattern.strip_suffix('/').unwrap_or(pattern);
@@ -81,12 +138,28 @@ impl MatchSpec {
             .collect();
 
         // Use the new exclude patterns from -X/--exclude
-        let exclude_glob = args
+        let mut exclude_glob: Vec<String> = args
             .exclude
             .iter()
             .map(|p| Self::normalize_pattern(p))
             .collect();
 
+        // --exclude-generated: add paths marked linguist-generated in .gitattributes
+        if args.exclude_generated {
+            exclude_glob.extend(
+                linguist_generated_patterns(target_path)
+                    .iter()
+                    .map(|p| Self::normalize_pattern(p)),
+            );
+        }
+
+        // --exclude-submodules: list submodule dirs from .gitmodules as leaves
+        let shallow_dirs = if args.exclude_submodules {
+            submodule_paths(target_path)
+        } else {
+            Vec::new()
+        };
+
         // Handle gitignore based on the new use_gitignore mode
         let respect_gitignore = match args.use_gitignore {
             crate::cli::UseGitignoreMode::Always => true,
@@ -103,12 +176,39 @@ impl MatchSpec {
             include_glob,
             exclude_glob,
             respect_gitignore,
+            parent_gitignores: !args.ignore_parent_gitignore,
+            respect_ignore_files: args.respect_ignore,
+            extra_ignore_files: args.extra_ignore.iter().map(PathBuf::from).collect(),
+            mark_gitignored: args.include_gitignored,
             use_safety_preset: args.is_safe_mode(),
-            case_sensitive: true, // Could be extended with --ignore-case flag
+            case_sensitive: Self::globs_case_sensitive(args),
+            exclude_dot_dirs: args.exclude_dot_dirs,
+            shallow_dirs,
+            mtime_range: Self::mtime_range(args, SystemTime::now()),
+            hide_lockfiles: args.hide_lockfiles,
+            max_file_size: args.exclude_larger_than,
             _keep_dirs_until_pruned: true,
         }
     }
 
+    /// Whether user globs match case-sensitively. Windows and macOS
+    /// filesystems fold case, so globs do too unless --case-sensitive asks
+    /// otherwise.
+    pub fn globs_case_sensitive(args: &Args) -> bool {
+        args.case_sensitive || !cfg!(any(windows, target_os = "macos"))
+    }
+
+    /// Window from `--modified-since` and `--older-than`, relative to `now`
+    fn mtime_range(args: &Args, now: SystemTime) -> Option<MtimeRange> {
+        if args.modified_since.is_none() && args.older_than.is_none() {
+            return None;
+        }
+        Some(MtimeRange {
+            oldest: args.modified_since.and_then(|d| now.checked_sub(d)),
+            newest: args.older_than.and_then(|d| now.checked_sub(d)),
+        })
+    }
+
     /// Check if a path is inside a git repository by walking up to find `.git`.
     fn is_inside_git_repo(path: &std::path::Path) -> bool {
         let mut current = path;
@@ -161,11 +261,47 @@ impl MatchSpec {
         self
     }
 
+    #[allow(dead_code)] // Used in tests
+    pub fn with_parent_gitignores(mut self, parents: bool) -> Self {
+        self.parent_gitignores = parents;
+        self
+    }
+
+    #[allow(dead_code)] // Used in tests
+    pub fn with_mark_gitignored(mut self, mark: bool) -> Self {
+        self.mark_gitignored = mark;
+        self
+    }
+
+    #[allow(dead_code)] // Used in tests
+    pub fn with_hide_lockfiles(mut self, hide: bool) -> Self {
+        self.hide_lockfiles = hide;
+        self
+    }
+
+    #[allow(dead_code)] // Used in tests
+    pub fn with_max_file_size(mut self, limit: Option<u64>) -> Self {
+        self.max_file_size = limit;
+        self
+    }
+
     #[allow(dead_code)] // Used in tests
     pub fn with_case_sensitive(mut self, sensitive: bool) -> Self {
         self.case_sensitive = sensitive;
         self
     }
+
+    #[allow(dead_code)] // Used in tests
+    pub fn with_mtime_range(mut self, range: MtimeRange) -> Self {
+        self.mtime_range = Some(range);
+        self
+    }
+
+    #[allow(dead_code)] // Used in tests
+    pub fn with_exclude_dot_dirs(mut self, exclude: bool) -> Self {
+        self.exclude_dot_dirs = exclude;
+        self
+    }
 }
 
 #[cfg(test)]
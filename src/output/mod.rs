@@ -1 +1,3 @@
+pub mod require;
+pub mod split;
 pub mod stats;
@@ -0,0 +1,160 @@
+use crate::output::stats::Stats;
+
+/// A `--require` predicate such as `files>=10` or `.rs<50`, checked against
+/// the final stats
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    metric: Metric,
+    op: Op,
+    value: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Metric {
+    Files,
+    Dirs,
+    Loc,
+    /// Files with this extension (`.rs`)
+    Ext(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Ge,
+    Le,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+}
+
+/// Two-character operators first so `>=` is not read as `>`
+const OPS: &[(&str, Op)] = &[
+    (">=", Op::Ge),
+    ("<=", Op::Le),
+    ("==", Op::Eq),
+    ("!=", Op::Ne),
+    (">", Op::Gt),
+    ("<", Op::Lt),
+    ("=", Op::Eq),
+];
+
+/// Parse `METRIC OP N` where METRIC is `files`, `dirs`, `loc` or `.EXT`
+pub fn parse_requirement(s: &str) -> Result<Requirement, String> {
+    let err = || {
+        format!(
+            "invalid requirement '{}' (expected e.g. files>=10, loc<5000, .rs>0)",
+            s
+        )
+    };
+    let start = s.find(['<', '>', '=', '!']).ok_or_else(err)?;
+    let (key, rest) = s.split_at(start);
+    let (symbol, op) = OPS
+        .iter()
+        .find(|(symbol, _)| rest.starts_with(symbol))
+        .ok_or_else(err)?;
+    let value = rest[symbol.len()..].trim().parse().map_err(|_| err())?;
+
+    let metric = match key.trim() {
+        "files" => Metric::Files,
+        "dirs" => Metric::Dirs,
+        "loc" => Metric::Loc,
+        key => match key.strip_prefix('.') {
+            Some(ext) if !ext.is_empty() => Metric::Ext(ext.to_string()),
+            _ => return Err(err()),
+        },
+    };
+
+    Ok(Requirement {
+        metric,
+        op: *op,
+        value,
+    })
+}
+
+impl Requirement {
+    /// `Err` with a message naming the actual value when the predicate fails
+    pub fn check(&self, stats: &Stats) -> Result<(), String> {
+        let actual = match &self.metric {
+            Metric::Files => stats.total_files(),
+            Metric::Dirs => stats.total_dirs(),
+            Metric::Loc => stats
+                .total_loc()
+                .ok_or_else(|| format!("{}: lines were not counted (--loc off)", self))?,
+            Metric::Ext(ext) => stats.extension_count(ext),
+        };
+        let holds = match self.op {
+            Op::Ge => actual >= self.value,
+            Op::Le => actual <= self.value,
+            Op::Eq => actual == self.value,
+            Op::Ne => actual != self.value,
+            Op::Gt => actual > self.value,
+            Op::Lt => actual < self.value,
+        };
+        if holds {
+            Ok(())
+        } else {
+            Err(format!("{} not met (actual: {})", self, actual))
+        }
+    }
+}
+
+impl std::fmt::Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let metric = match &self.metric {
+            Metric::Files => "files".to_string(),
+            Metric::Dirs => "dirs".to_string(),
+            Metric::Loc => "loc".to_string(),
+            Metric::Ext(ext) => format!(".{}", ext),
+        };
+        let symbol = OPS
+            .iter()
+            .find(|(_, op)| *op == self.op)
+            .map_or("", |(symbol, _)| symbol);
+        write!(f, "{}{}{}", metric, symbol, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::FileType;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_requirement() {
+        let req = parse_requirement("files>=10").unwrap();
+        assert_eq!(req.metric, Metric::Files);
+        assert_eq!(req.op, Op::Ge);
+        assert_eq!(req.value, 10);
+
+        let req = parse_requirement(" .rs > 0").unwrap();
+        assert_eq!(req.metric, Metric::Ext("rs".to_string()));
+        assert_eq!(req.op, Op::Gt);
+        assert_eq!(req.to_string(), ".rs>0");
+
+        assert_eq!(parse_requirement("loc=5").unwrap().op, Op::Eq);
+        assert!(parse_requirement("files").is_err());
+        assert!(parse_requirement("bytes>1").is_err());
+        assert!(parse_requirement("files>=ten").is_err());
+        assert!(parse_requirement(".>1").is_err());
+    }
+
+    #[test]
+    fn test_check_requirement() {
+        let mut stats = Stats::new();
+        stats.add_file(FileType::Rust, String::new(), Path::new("main.rs"));
+        stats.add_file(FileType::Rust, String::new(), Path::new("lib.rs"));
+
+        assert!(parse_requirement("files==2").unwrap().check(&stats).is_ok());
+        assert!(parse_requirement(".rs>=2").unwrap().check(&stats).is_ok());
+        assert_eq!(
+            parse_requirement("files>=10").unwrap().check(&stats),
+            Err("files>=10 not met (actual: 2)".to_string())
+        );
+        assert!(parse_requirement("loc>0").unwrap().check(&stats).is_err());
+
+        stats.add_loc(FileType::Rust, 40);
+        assert!(parse_requirement("loc<=40").unwrap().check(&stats).is_ok());
+    }
+}
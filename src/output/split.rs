@@ -0,0 +1,57 @@
+use crate::cli::Args;
+use crate::fs_tree::Node;
+use crate::render::pipeline::root_name;
+use crate::render::{PipeRenderer, Renderer};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Write one markdown file per top-level directory plus an `index.md` linking to them.
+/// A directory whose file name would clash (`index/`, or `Src/` next to `src/`)
+/// gets a numeric suffix (`index-1.md`). Returns the paths written, index last.
+pub fn write_split(root: &Node, args: &Args, out_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(out_dir)?;
+
+    let mut written = Vec::new();
+    let mut index = format!("# {}\n\n", root_name(root));
+    // Compared case-folded, for case-insensitive filesystems
+    let mut used: HashSet<String> = HashSet::from(["index".to_string()]);
+
+    for child in root.children.iter().filter(|c| c.is_dir) {
+        let file_name = format!("{}.md", unique_stem(&child.name, &mut used));
+        let mut renderer = PipeRenderer::new(args);
+        let body = renderer.render_tree(child);
+
+        let path = out_dir.join(&file_name);
+        fs::write(&path, format!("# {}/\n\n{}", child.name, body))?;
+        written.push(path);
+
+        index.push_str(&format!("- [{}/]({})\n", child.name, file_name));
+    }
+
+    let root_files: Vec<&Node> = root.children.iter().filter(|c| !c.is_dir).collect();
+    if !root_files.is_empty() {
+        index.push_str("\n**Top-level files**:\n");
+        for file in root_files {
+            index.push_str(&format!("- {}\n", file.name));
+        }
+    }
+
+    let index_path = out_dir.join("index.md");
+    fs::write(&index_path, index)?;
+    written.push(index_path);
+
+    Ok(written)
+}
+
+/// `name`, or `name-1`, `name-2`, ... when that stem is already taken
+fn unique_stem(name: &str, used: &mut HashSet<String>) -> String {
+    let mut stem = name.to_string();
+    let mut n = 0;
+    while !used.insert(stem.to_lowercase()) {
+        n += 1;
+        stem = format!("{}-{}", name, n);
+    }
+    stem
+}
@@ -1,4 +1,4 @@
-use crate::cli::StatsMode;
+use crate::cli::{Args, ExtCase, NumberFormat, StatsMetric, StatsMode, StatsThreshold};
 use crate::profile::FileType;
 use crate::terminal::capabilities::ProgressChars;
 use std::collections::HashMap;
@@ -15,6 +15,13 @@ pub struct Stats {
     total_dirs: usize,
     total_files: usize,
     total_loc: Option<usize>,
+    ext_case: ExtCase,
+    threshold: Option<StatsThreshold>,
+    skip_no_ext: bool,
+    number_format: Option<NumberFormat>,
+    metric: StatsMetric,
+    depth_counts: Vec<usize>,
+    depth_summary: bool,
 }
 
 #[derive(Default)]
@@ -34,6 +41,71 @@ impl Stats {
             total_dirs: 0,
             total_files: 0,
             total_loc: None,
+            ext_case: ExtCase::Fold,
+            threshold: None,
+            skip_no_ext: false,
+            number_format: None,
+            metric: StatsMetric::Files,
+            depth_counts: Vec::new(),
+            depth_summary: false,
+        }
+    }
+
+    /// Create a stats collector configured from CLI arguments
+    pub fn from_args(args: &Args) -> Self {
+        Self::new()
+            .with_ext_case(args.ext_case)
+            .with_threshold(args.stats_threshold)
+            .with_skip_no_ext(args.stats_skip_no_ext)
+            .with_number_format(args.number_format)
+            .with_metric(args.stats_metric)
+            .with_depth_summary(args.depth_summary)
+    }
+
+    /// Set whether extensions are case-folded when grouping
+    pub fn with_ext_case(mut self, ext_case: ExtCase) -> Self {
+        self.ext_case = ext_case;
+        self
+    }
+
+    /// Leave extensionless files out of the by-extension breakdown
+    pub fn with_skip_no_ext(mut self, skip: bool) -> Self {
+        self.skip_no_ext = skip;
+        self
+    }
+
+    /// Set how counts and LOC are printed (`None` keeps plain counts, compact LOC)
+    pub fn with_number_format(mut self, number_format: Option<NumberFormat>) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Set whether the type breakdown measures files or lines of code
+    pub fn with_metric(mut self, metric: StatsMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Append a `L0:3 L1:12` files-per-depth line to the stats
+    pub fn with_depth_summary(mut self, depth_summary: bool) -> Self {
+        self.depth_summary = depth_summary;
+        self
+    }
+
+    /// Set the minimum share for a type to get its own row in full stats
+    pub fn with_threshold(mut self, threshold: Option<StatsThreshold>) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Whether a type with `count` files clears the `--stats-threshold`
+    fn meets_threshold(&self, count: usize) -> bool {
+        match self.threshold {
+            None => true,
+            Some(StatsThreshold::Count(min)) => count >= min,
+            Some(StatsThreshold::Percent(min)) => {
+                count as f32 * 100.0 / self.total_files.max(1) as f32 >= min
+            }
         }
     }
 
@@ -44,6 +116,7 @@ impl Stats {
         self.total_dirs = 0;
         self.total_files = 0;
         self.total_loc = None;
+        self.depth_counts.clear();
     }
 
     /// Add a file with its type
@@ -64,7 +137,10 @@ impl Stats {
 
         // Track by extension
         if let Some(ext) = path.extension() {
-            let ext_str = ext.to_string_lossy().to_lowercase();
+            let ext_str = match self.ext_case {
+                ExtCase::Fold => ext.to_string_lossy().to_lowercase(),
+                ExtCase::Preserve => ext.to_string_lossy().to_string(),
+            };
             *self.extension_counts.entry(ext_str).or_insert(0) += 1;
         } else {
             *self
@@ -74,17 +150,49 @@ impl Stats {
         }
     }
 
+    /// Count a file at `depth` (0 for files directly under the root)
+    pub fn add_file_depth(&mut self, depth: usize) {
+        if self.depth_counts.len() <= depth {
+            self.depth_counts.resize(depth + 1, 0);
+        }
+        self.depth_counts[depth] += 1;
+    }
+
+    /// Number of files at each depth, indexed by depth
+    #[allow(dead_code)]
+    pub fn depth_counts(&self) -> &[usize] {
+        &self.depth_counts
+    }
+
     /// Add a directory
     pub fn add_directory(&mut self) {
         self.total_dirs += 1;
     }
 
     /// Get the total number of directories
-    #[allow(dead_code)]
     pub fn total_dirs(&self) -> usize {
         self.total_dirs
     }
 
+    /// Get the total number of files
+    pub fn total_files(&self) -> usize {
+        self.total_files
+    }
+
+    /// Get the total lines of code (`None` when nothing was counted)
+    pub fn total_loc(&self) -> Option<usize> {
+        self.total_loc
+    }
+
+    /// Number of files with extension `ext` (folded like `--ext-case`)
+    pub fn extension_count(&self, ext: &str) -> usize {
+        let key = match self.ext_case {
+            ExtCase::Fold => ext.to_lowercase(),
+            ExtCase::Preserve => ext.to_string(),
+        };
+        self.extension_counts.get(&key).copied().unwrap_or(0)
+    }
+
     /// Add LOC count for a file type
     pub fn add_loc(&mut self, file_type: FileType, lines: usize) {
         if let Some(stats) = self.file_types.get_mut(&file_type) {
@@ -93,13 +201,64 @@ impl Stats {
         self.total_loc = Some(self.total_loc.unwrap_or(0) + lines);
     }
 
+    /// Add another collector's totals into this one (e.g. separately scanned
+    /// roots). Settings such as `--ext-case` stay this collector's own.
+    #[allow(dead_code)]
+    pub fn merge(&mut self, other: &Stats) {
+        self.total_dirs += other.total_dirs;
+        self.total_files += other.total_files;
+        if let Some(loc) = other.total_loc {
+            self.total_loc = Some(self.total_loc.unwrap_or(0) + loc);
+        }
+
+        for (file_type, theirs) in &other.file_types {
+            let ours = self
+                .file_types
+                .entry(*file_type)
+                .or_insert_with(|| TypeStats {
+                    emoji: theirs.emoji.clone(),
+                    name: theirs.name.clone(),
+                    ..Default::default()
+                });
+            ours.count += theirs.count;
+            if let Some(loc) = theirs.loc {
+                ours.loc = Some(ours.loc.unwrap_or(0) + loc);
+            }
+        }
+
+        for (ext, count) in &other.extension_counts {
+            *self.extension_counts.entry(ext.clone()).or_insert(0) += count;
+        }
+
+        if self.depth_counts.len() < other.depth_counts.len() {
+            self.depth_counts.resize(other.depth_counts.len(), 0);
+        }
+        for (ours, theirs) in self.depth_counts.iter_mut().zip(&other.depth_counts) {
+            *ours += theirs;
+        }
+    }
+
     /// Generate stats output based on mode
     pub fn generate_output(&self, mode: StatsMode, use_unicode: bool) -> String {
-        match mode {
+        let mut output = match mode {
             StatsMode::Off => self.generate_footer(),
             StatsMode::Min => self.generate_minimal(),
             StatsMode::Full => self.generate_full(use_unicode),
+        };
+        if self.depth_summary {
+            output.push_str(&format!("**Depth**: {}\n", self.depth_line()));
         }
+        output
+    }
+
+    /// Files per depth as `L0:3 L1:12 L2:45`
+    fn depth_line(&self) -> String {
+        self.depth_counts
+            .iter()
+            .enumerate()
+            .map(|(depth, count)| format!("L{}:{}", depth, self.number(*count)))
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 
     /// Generate the basic stats footer (similar to old StatsCollector)
@@ -107,12 +266,16 @@ impl Stats {
         let mut footer = String::new();
 
         footer.push_str("**Stats**\n");
-        footer.push_str(&format!("- Dirs: {}\n", self.total_dirs));
-        footer.push_str(&format!("- Files: {}\n", self.total_files));
+        footer.push_str(&format!("- Dirs: {}\n", self.number(self.total_dirs)));
+        footer.push_str(&format!("- Files: {}\n", self.number(self.total_files)));
 
         // Get top extensions by count
         if !self.extension_counts.is_empty() {
-            let mut ext_vec: Vec<(&String, &usize)> = self.extension_counts.iter().collect();
+            let mut ext_vec: Vec<(&String, &usize)> = self
+                .extension_counts
+                .iter()
+                .filter(|(ext, _)| !(self.skip_no_ext && ext.as_str() == "(no ext)"))
+                .collect();
             ext_vec.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
 
             // Show top 5 extensions
@@ -121,9 +284,9 @@ impl Stats {
                 .take(5)
                 .map(|(ext, count)| {
                     if ext == &"(no ext)" {
-                        format!("no-ext({})", count)
+                        format!("no-ext({})", self.number(**count))
                     } else {
-                        format!("{}({})", ext, count)
+                        format!("{}({})", ext, self.number(**count))
                     }
                 })
                 .collect();
@@ -142,11 +305,12 @@ impl Stats {
 
         output.push_str(&format!(
             "**Stats**: 📂 {} dirs • 📄 {} files",
-            self.total_dirs, self.total_files
+            self.number(self.total_dirs),
+            self.number(self.total_files)
         ));
 
         if let Some(loc) = self.total_loc {
-            output.push_str(&format!(" • 🧾 ~{} LOC", format_count(loc)));
+            output.push_str(&format!(" • 🧾 ~{} LOC", self.loc_number(loc)));
         }
 
         output.push('\n');
@@ -160,11 +324,12 @@ impl Stats {
         // Totals line
         output.push_str(&format!(
             "**Totals**: 📂 {} dirs • 📄 {} files",
-            self.total_dirs, self.total_files
+            self.number(self.total_dirs),
+            self.number(self.total_files)
         ));
 
         if let Some(loc) = self.total_loc {
-            output.push_str(&format!(" • 🧾 ~{} LOC", format_count(loc)));
+            output.push_str(&format!(" • 🧾 ~{} LOC", self.loc_number(loc)));
         }
 
         output.push('\n');
@@ -173,9 +338,14 @@ impl Stats {
         if !self.file_types.is_empty() {
             output.push_str("\n**By type**:\n");
 
-            // Sort by count descending
-            let mut types: Vec<_> = self.file_types.iter().collect();
-            types.sort_by(|a, b| b.1.count.cmp(&a.1.count));
+            // Sort by the chosen metric descending, then by name so ties
+            // don't depend on HashMap iteration order
+            let mut types: Vec<_> = self.file_types.values().collect();
+            types.sort_by(|a, b| {
+                self.metric_of(b)
+                    .cmp(&self.metric_of(a))
+                    .then_with(|| a.name.cmp(&b.name))
+            });
 
             let chars = if use_unicode {
                 ProgressChars::unicode()
@@ -183,26 +353,70 @@ impl Stats {
                 ProgressChars::ascii()
             };
 
-            for (_file_type, stats) in types.iter().take(8) {
-                let percentage = (stats.count as f32 / self.total_files as f32) * 100.0;
-                let bar = self.render_bar(percentage, 15, chars.clone());
+            // Types under --stats-threshold collapse into a single "Other" row
+            let (shown, rare): (Vec<_>, Vec<_>) = types
+                .into_iter()
+                .partition(|t| self.meets_threshold(t.count));
 
+            for stats in shown.iter().take(8) {
                 let emoji = if !stats.emoji.is_empty() {
                     format!("{} ", stats.emoji)
                 } else {
                     String::new()
                 };
+                let label = format!("{}{}", emoji, stats.name);
+                output.push_str(&self.type_row(&label, self.metric_of(stats), chars.clone()));
+            }
 
-                output.push_str(&format!(
-                    "- {}{}: {} ({:.0}%) {}\n",
-                    emoji, stats.name, stats.count, percentage, bar
-                ));
+            if !rare.is_empty() {
+                let count = rare.iter().map(|t| self.metric_of(t)).sum();
+                let label = format!("Other ({} types)", rare.len());
+                output.push_str(&self.type_row(&label, count, chars.clone()));
             }
         }
 
         output
     }
 
+    /// A type's value under `--stats-metric`
+    fn metric_of(&self, stats: &TypeStats) -> usize {
+        match self.metric {
+            StatsMetric::Files => stats.count,
+            StatsMetric::Loc => stats.loc.unwrap_or(0),
+        }
+    }
+
+    /// Render one `- label: value (pct%) bar` row of the type breakdown
+    fn type_row(&self, label: &str, value: usize, chars: ProgressChars) -> String {
+        let (total, shown) = match self.metric {
+            StatsMetric::Files => (self.total_files, self.number(value)),
+            StatsMetric::Loc => (
+                self.total_loc.unwrap_or(0),
+                format!("{} LOC", self.loc_number(value)),
+            ),
+        };
+        let percentage = (value as f32 / total.max(1) as f32) * 100.0;
+        let bar = self.render_bar(percentage, 15, chars);
+        format!("- {}: {} ({:.0}%) {}\n", label, shown, percentage, bar)
+    }
+
+    /// Format a count per `--number-format` (plain by default)
+    fn number(&self, n: usize) -> String {
+        match self.number_format {
+            None | Some(NumberFormat::Plain) => n.to_string(),
+            Some(NumberFormat::Grouped) => format_grouped(n),
+            Some(NumberFormat::Compact) => format_count(n),
+        }
+    }
+
+    /// Format a LOC total per `--number-format` (compact by default)
+    fn loc_number(&self, n: usize) -> String {
+        match self.number_format {
+            None => format_count(n),
+            Some(_) => self.number(n),
+        }
+    }
+
     /// Render a progress bar
     fn render_bar(&self, percentage: f32, width: usize, chars: ProgressChars) -> String {
         let filled = ((percentage * width as f32 / 100.0).round() as usize).min(width);
@@ -233,6 +447,19 @@ fn format_count(count: usize) -> String {
     }
 }
 
+/// Format numbers with comma thousands separators
+fn format_grouped(count: usize) -> String {
+    let digits = count.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,6 +499,161 @@ mod tests {
         assert!(footer.contains("Files: 3"));
     }
 
+    #[test]
+    fn test_number_format() {
+        let mut stats = Stats::new();
+        for i in 0..1234 {
+            stats.add_file(
+                FileType::Rust,
+                String::new(),
+                Path::new(&format!("f{}.rs", i)),
+            );
+        }
+
+        let default = stats.generate_output(StatsMode::Min, false);
+        assert!(default.contains("1234 files"), "default: {}", default);
+
+        let grouped = stats.with_number_format(Some(NumberFormat::Grouped));
+        let out = grouped.generate_output(StatsMode::Min, false);
+        assert!(out.contains("1,234 files"), "grouped: {}", out);
+
+        let compact = grouped.with_number_format(Some(NumberFormat::Compact));
+        let out = compact.generate_output(StatsMode::Min, false);
+        assert!(out.contains("1.2K files"), "compact: {}", out);
+
+        assert_eq!(format_grouped(0), "0");
+        assert_eq!(format_grouped(999), "999");
+        assert_eq!(format_grouped(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn test_stats_metric_loc() {
+        let mut stats = Stats::new().with_metric(StatsMetric::Loc);
+        // Three small Python files, one large Rust file
+        for name in ["a.py", "b.py", "c.py"] {
+            stats.add_file(FileType::Python, String::new(), Path::new(name));
+            stats.add_loc(FileType::Python, 10);
+        }
+        stats.add_file(FileType::Rust, String::new(), Path::new("main.rs"));
+        stats.add_loc(FileType::Rust, 970);
+
+        let output = stats.generate_output(StatsMode::Full, false);
+        let rows: Vec<&str> = output.lines().filter(|l| l.starts_with("- ")).collect();
+        assert!(
+            rows[0].contains("Rust: 970 LOC (97%)"),
+            "output: {}",
+            output
+        );
+        assert!(
+            rows[1].contains("Python: 30 LOC (3%)"),
+            "output: {}",
+            output
+        );
+
+        // By files, Python leads instead
+        let stats = stats.with_metric(StatsMetric::Files);
+        let output = stats.generate_output(StatsMode::Full, false);
+        let first = output.lines().find(|l| l.starts_with("- ")).unwrap();
+        assert!(first.contains("Python: 3 (75%)"), "output: {}", output);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut left = Stats::new();
+        left.add_directory();
+        left.add_file(FileType::Rust, String::new(), Path::new("main.rs"));
+        left.add_loc(FileType::Rust, 100);
+        left.add_file_depth(0);
+
+        let mut right = Stats::new();
+        right.add_directory();
+        right.add_directory();
+        right.add_file(FileType::Rust, String::new(), Path::new("lib.rs"));
+        right.add_loc(FileType::Rust, 50);
+        right.add_file_depth(1);
+        right.add_file(FileType::Markdown, String::new(), Path::new("README.md"));
+        right.add_file_depth(1);
+
+        left.merge(&right);
+
+        assert_eq!(left.total_dirs, 3);
+        assert_eq!(left.total_files, 3);
+        assert_eq!(left.total_loc, Some(150));
+        assert_eq!(left.file_types[&FileType::Rust].count, 2);
+        assert_eq!(left.file_types[&FileType::Rust].loc, Some(150));
+        assert_eq!(left.file_types[&FileType::Markdown].count, 1);
+        assert_eq!(left.file_types[&FileType::Markdown].loc, None);
+        assert_eq!(left.extension_counts["rs"], 2);
+        assert_eq!(left.extension_counts["md"], 1);
+        assert_eq!(left.depth_counts(), &[1, 2]);
+    }
+
+    #[test]
+    fn test_depth_summary() {
+        let mut stats = Stats::new().with_depth_summary(true);
+        for depth in [0, 1, 1, 2, 1] {
+            stats.add_file_depth(depth);
+        }
+        assert_eq!(stats.depth_counts(), &[1, 3, 1]);
+
+        let output = stats.generate_output(StatsMode::Min, false);
+        assert!(
+            output.ends_with("**Depth**: L0:1 L1:3 L2:1\n"),
+            "output: {}",
+            output
+        );
+
+        // Off by default
+        let output = Stats::new().generate_output(StatsMode::Min, false);
+        assert!(!output.contains("**Depth**"));
+    }
+
+    #[test]
+    fn test_skip_no_ext() {
+        let mut stats = Stats::new();
+        stats.add_file(FileType::Makefile, String::new(), Path::new("Makefile"));
+        stats.add_file(FileType::Rust, String::new(), Path::new("main.rs"));
+        assert!(stats.generate_footer().contains("no-ext(1)"));
+
+        let mut stats = Stats::new().with_skip_no_ext(true);
+        stats.add_file(FileType::Makefile, String::new(), Path::new("Makefile"));
+        stats.add_file(FileType::Rust, String::new(), Path::new("main.rs"));
+        let footer = stats.generate_footer();
+        assert!(!footer.contains("no-ext"), "footer: {}", footer);
+        assert!(footer.contains("rs(1)"), "footer: {}", footer);
+        // Extensionless files still count toward the totals
+        assert!(footer.contains("Files: 2"), "footer: {}", footer);
+    }
+
+    #[test]
+    fn test_ext_case_fold_and_preserve() {
+        let mut folded = Stats::new();
+        folded.add_file(FileType::Markdown, String::new(), Path::new("README.MD"));
+        folded.add_file(FileType::Markdown, String::new(), Path::new("guide.md"));
+        assert_eq!(folded.extension_counts.get("md"), Some(&2));
+        assert_eq!(folded.extension_counts.get("MD"), None);
+
+        let mut preserved = Stats::new().with_ext_case(ExtCase::Preserve);
+        preserved.add_file(FileType::Markdown, String::new(), Path::new("README.MD"));
+        preserved.add_file(FileType::Markdown, String::new(), Path::new("guide.md"));
+        assert_eq!(preserved.extension_counts.get("md"), Some(&1));
+        assert_eq!(preserved.extension_counts.get("MD"), Some(&1));
+    }
+
+    #[test]
+    fn test_full_breaks_count_ties_by_name() {
+        let mut stats = Stats::new();
+        stats.add_file(FileType::Rust, String::new(), Path::new("main.rs"));
+        stats.add_file(FileType::Python, String::new(), Path::new("app.py"));
+        stats.add_file(FileType::Go, String::new(), Path::new("main.go"));
+
+        let output = stats.generate_output(StatsMode::Full, false);
+        let go = output.find("- Go:").unwrap();
+        let python = output.find("- Python:").unwrap();
+        let rust = output.find("- Rust:").unwrap();
+        assert!(go < python && python < rust, "{}", output);
+    }
+
     #[test]
     fn test_stats_reset() {
         let mut stats = Stats::new();
@@ -2,6 +2,9 @@ use crate::profile::FileType;
 use std::collections::HashMap;
 use std::path::Path;
 
+/// Environment variable consulted when no `--emoji-map` is given
+pub const EMOJI_MAP_ENV: &str = "TREE2MD_EMOJI_MAP";
+
 /// Manages emoji assignments for files and directories
 pub struct EmojiMapper {
     /// Custom emoji overrides by extension
@@ -10,6 +13,9 @@ pub struct EmojiMapper {
     /// Custom emoji overrides by file type
     type_overrides: HashMap<FileType, String>,
 
+    /// Custom emoji overrides by directory name (keys written as `name/`)
+    dir_overrides: HashMap<String, String>,
+
     /// Whether emojis are enabled
     enabled: bool,
 }
@@ -19,6 +25,7 @@ impl EmojiMapper {
         Self {
             extension_overrides: HashMap::new(),
             type_overrides: HashMap::new(),
+            dir_overrides: HashMap::new(),
             enabled,
         }
     }
@@ -33,10 +40,40 @@ impl EmojiMapper {
         self.type_overrides.insert(file_type, emoji);
     }
 
+    /// Add a custom emoji override for directories with the given name
+    pub fn add_dir_override(&mut self, name: String, emoji: String) {
+        self.dir_overrides.insert(name, emoji);
+    }
+
+    /// Override the fallback emoji used when no type or extension mapping matches
+    pub fn set_unknown_emoji(&mut self, emoji: String) {
+        self.type_overrides.insert(FileType::Unknown, emoji);
+    }
+
     /// Load emoji mappings from a TOML file
     pub fn load_from_file(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
         let content = std::fs::read_to_string(path)?;
-        let mappings: toml::Value = toml::from_str(&content)?;
+        self.load_from_toml(&content)
+    }
+
+    /// Load emoji mappings from `TREE2MD_EMOJI_MAP`: either the TOML of an
+    /// emoji map file or inline `--emoji` entries separated by `;`
+    /// (`.rs=🦀;docs/=📚`)
+    pub fn load_from_env_value(&mut self, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if toml::from_str::<toml::Value>(value).is_ok() {
+            return self.load_from_toml(value);
+        }
+        for entry in value.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            if !entry.contains('=') {
+                return Err(format!("expected KEY=EMOJI, got '{}'", entry).into());
+            }
+            self.parse_cli_emoji(entry);
+        }
+        Ok(())
+    }
+
+    fn load_from_toml(&mut self, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mappings: toml::Value = toml::from_str(content)?;
 
         if let Some(table) = mappings.as_table() {
             // Load extension mappings
@@ -51,6 +88,18 @@ impl EmojiMapper {
                 }
             }
 
+            // Load directory-name mappings
+            if let Some(dirs) = table.get("dirs").and_then(|v| v.as_table()) {
+                for (name, emoji) in dirs {
+                    if let Some(emoji_str) = emoji.as_str() {
+                        self.add_dir_override(
+                            name.trim_end_matches('/').to_string(),
+                            emoji_str.to_string(),
+                        );
+                    }
+                }
+            }
+
             // Load type mappings
             if let Some(types) = table.get("types").and_then(|v| v.as_table()) {
                 for (type_name, emoji) in types {
@@ -82,6 +131,17 @@ impl EmojiMapper {
             return String::new();
         }
 
+        // Directory-name overrides apply only to directories
+        if file_type == FileType::Directory {
+            if let Some(emoji) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| self.dir_overrides.get(n))
+            {
+                return emoji.clone();
+            }
+        }
+
         // Check extension overrides first
         if let Some(ext) = path.extension() {
             if let Some(ext_str) = ext.to_str() {
@@ -100,7 +160,7 @@ impl EmojiMapper {
         file_type.default_emoji().to_string()
     }
 
-    /// Parse CLI emoji arguments (format: ".ext=emoji" or "type=emoji")
+    /// Parse CLI emoji arguments (format: ".ext=emoji", "dir/=emoji" or "type=emoji")
     pub fn parse_cli_emoji(&mut self, arg: &str) {
         if let Some(eq_pos) = arg.find('=') {
             let (key, emoji) = arg.split_at(eq_pos);
@@ -109,6 +169,9 @@ impl EmojiMapper {
             if let Some(stripped) = key.strip_prefix('.') {
                 // Extension override
                 self.add_extension_override(stripped.to_string(), emoji.to_string());
+            } else if let Some(name) = key.strip_suffix('/').filter(|n| !n.is_empty()) {
+                // Directory-name override
+                self.add_dir_override(name.to_string(), emoji.to_string());
             } else {
                 // Try to parse as type
                 let file_type = match key.to_lowercase().as_str() {
@@ -161,6 +224,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_emoji_map_env_value() {
+        let mut mapper = EmojiMapper::new(true);
+        mapper.load_from_env_value(".rs=🚀; docs/=📚;").unwrap();
+        assert_eq!(mapper.get_emoji(Path::new("main.rs"), FileType::Rust), "🚀");
+        assert_eq!(
+            mapper.get_emoji(Path::new("docs"), FileType::Directory),
+            "📚"
+        );
+
+        let mut mapper = EmojiMapper::new(true);
+        mapper
+            .load_from_env_value("[extensions]\npy = \"🐉\"\n")
+            .unwrap();
+        assert_eq!(
+            mapper.get_emoji(Path::new("app.py"), FileType::Python),
+            "🐉"
+        );
+
+        assert!(EmojiMapper::new(true).load_from_env_value("rs").is_err());
+    }
+
     #[test]
     fn test_emoji_override_by_extension() {
         let mut mapper = EmojiMapper::new(true);
@@ -188,6 +273,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unknown_emoji_override() {
+        let mut mapper = EmojiMapper::new(true);
+        mapper.set_unknown_emoji("❓".to_string());
+
+        let weird = Path::new("data.qzx");
+        assert_eq!(
+            mapper.get_emoji(weird, FileType::classify_path(weird)),
+            "❓"
+        );
+        // Known types keep their own emoji
+        assert_eq!(mapper.get_emoji(Path::new("main.rs"), FileType::Rust), "🦀");
+
+        // Extension overrides still win over the fallback
+        mapper.add_extension_override("qzx".to_string(), "🧬".to_string());
+        assert_eq!(mapper.get_emoji(weird, FileType::Unknown), "🧬");
+    }
+
     #[test]
     fn test_emoji_parse_cli() {
         let mut mapper = EmojiMapper::new(true);
@@ -201,6 +304,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_emoji_parse_cli_dir_key() {
+        let mut mapper = EmojiMapper::new(true);
+        mapper.parse_cli_emoji("src/=📦");
+
+        assert_eq!(
+            mapper.get_emoji(Path::new("project/src"), FileType::Directory),
+            "📦"
+        );
+        // Other directories keep the default, and files named `src` are unaffected
+        assert_eq!(
+            mapper.get_emoji(Path::new("docs"), FileType::Directory),
+            FileType::Directory.default_emoji()
+        );
+        assert_ne!(mapper.get_emoji(Path::new("src"), FileType::Unknown), "📦");
+    }
+
     #[test]
     fn test_parse_cli_emoji_invalid() {
         let mut mapper = EmojiMapper::new(true);
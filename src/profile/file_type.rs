@@ -95,6 +95,35 @@ impl FileType {
         }
     }
 
+    /// ANSI color for `--color-by type`, grouped by category
+    pub fn ansi_color(&self) -> Option<&'static str> {
+        match self {
+            FileType::Rust
+            | FileType::Python
+            | FileType::Go
+            | FileType::JavaScript
+            | FileType::TypeScript
+            | FileType::Java
+            | FileType::CSharp
+            | FileType::CPlusPlus
+            | FileType::C
+            | FileType::Swift
+            | FileType::Kotlin
+            | FileType::Ruby
+            | FileType::Php
+            | FileType::Shell => Some("32"),
+            FileType::Markdown | FileType::Text | FileType::License => Some("34"),
+            FileType::Json | FileType::Yaml | FileType::Toml | FileType::Xml | FileType::Ini => {
+                Some("35")
+            }
+            FileType::Test => Some("36"),
+            FileType::Ignore | FileType::Lock | FileType::Dockerfile | FileType::Makefile => {
+                Some("33")
+            }
+            FileType::Directory | FileType::Unknown => None,
+        }
+    }
+
     /// Get a display name for this file type
     pub fn display_name(&self) -> &str {
         match self {
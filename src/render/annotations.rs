@@ -0,0 +1,103 @@
+use globset::{Glob, GlobMatcher};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// Path descriptions loaded from `--annotate-from`.
+///
+/// The file holds one `path: description` entry per line; blank lines and
+/// lines starting with `#` are skipped. Keys may be globs (`tests/*.rs`).
+#[derive(Debug, Clone, Default)]
+pub struct Annotations {
+    exact: HashMap<String, String>,
+    globs: Vec<(GlobMatcher, String)>,
+}
+
+impl Annotations {
+    /// Load annotations from a file
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self::parse(&content))
+    }
+
+    /// Parse annotations from `path: description` lines
+    pub fn parse(content: &str) -> Self {
+        let mut annotations = Self::default();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, description)) = line.split_once(':') else {
+                continue;
+            };
+            let key = normalize_key(key.trim());
+            let description = description.trim().to_string();
+            if key.is_empty() || description.is_empty() {
+                continue;
+            }
+
+            if key.contains(['*', '?', '[']) {
+                match Glob::new(&key) {
+                    Ok(glob) => annotations
+                        .globs
+                        .push((glob.compile_matcher(), description)),
+                    Err(e) => eprintln!("Warning: Invalid annotation pattern '{}': {}", key, e),
+                }
+            } else {
+                annotations.exact.insert(key, description);
+            }
+        }
+
+        annotations
+    }
+
+    /// Description for a display path; exact keys win over globs,
+    /// and earlier globs win over later ones
+    pub fn lookup(&self, display_path: &Path) -> Option<&str> {
+        let key = normalize_key(&display_path.to_string_lossy().replace('\\', "/"));
+        if let Some(description) = self.exact.get(&key) {
+            return Some(description);
+        }
+        self.globs
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(&key))
+            .map(|(_, description)| description.as_str())
+    }
+}
+
+/// Strip `./` prefixes and trailing slashes so `./src/` and `src` match alike
+fn normalize_key(key: &str) -> String {
+    key.trim_start_matches("./")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_lookup() {
+        let annotations = Annotations::parse(
+            "# project notes\n\
+             src/: core library\n\
+             ./src/main.rs: entry point\n\
+             tests/*.rs: integration test\n\
+             \n\
+             broken line without separator\n",
+        );
+
+        assert_eq!(annotations.lookup(Path::new("src")), Some("core library"));
+        assert_eq!(
+            annotations.lookup(Path::new("src/main.rs")),
+            Some("entry point")
+        );
+        assert_eq!(
+            annotations.lookup(Path::new("tests/cli.rs")),
+            Some("integration test")
+        );
+        assert_eq!(annotations.lookup(Path::new("src/lib.rs")), None);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::cli::{Args, LocMode};
+use crate::fs_tree::Node;
+use crate::render::pipeline::{collect_files, IrBuilder};
+use crate::render::renderer::{OutputFormat, Renderer};
+
+/// Flat renderer for `--only-files` and `--print0`.
+/// Produces a sorted list of relative file paths, each terminated by a
+/// newline, or by NUL under `--print0`.
+pub struct FlatRenderer {
+    ir_builder: IrBuilder,
+    terminator: char,
+}
+
+impl FlatRenderer {
+    pub fn new(args: &Args) -> Self {
+        // Paths alone need no line counts, unless a LOC filter decides which stay
+        let mut ir_builder = IrBuilder::new(args);
+        if !ir_builder.options().filters_loc() {
+            ir_builder = ir_builder.with_loc_mode(LocMode::Off);
+        }
+
+        Self {
+            ir_builder,
+            terminator: if args.print0 { '\0' } else { '\n' },
+        }
+    }
+}
+
+impl Renderer for FlatRenderer {
+    fn render_tree(&mut self, root: &Node) -> String {
+        let ir = self.ir_builder.build(root);
+
+        let mut paths: Vec<String> = collect_files(&ir)
+            .iter()
+            .map(|f| f.display_path.to_string_lossy().replace('\\', "/"))
+            .collect();
+        paths.sort();
+
+        let mut output = String::new();
+        for path in paths {
+            output.push_str(&path);
+            output.push(self.terminator);
+        }
+        output
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Flat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_flat_renderer_sorted_paths() {
+        let args = Args::parse_from(["tree2md", "--only-files", "--loc", "off"]);
+        let mut renderer = FlatRenderer::new(&args);
+
+        let root = Node {
+            name: "test".to_string(),
+            path: PathBuf::from("test"),
+            is_dir: true,
+            display_path: PathBuf::from("."),
+            children: vec![
+                Node {
+                    name: "src".to_string(),
+                    path: PathBuf::from("test/src"),
+                    is_dir: true,
+                    display_path: PathBuf::from("src"),
+                    children: vec![Node {
+                        name: "main.rs".to_string(),
+                        path: PathBuf::from("test/src/main.rs"),
+                        is_dir: false,
+                        display_path: PathBuf::from("src/main.rs"),
+                        children: vec![],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                Node {
+                    name: "Cargo.toml".to_string(),
+                    path: PathBuf::from("test/Cargo.toml"),
+                    is_dir: false,
+                    display_path: PathBuf::from("Cargo.toml"),
+                    children: vec![],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let output = renderer.render_tree(&root);
+        assert_eq!(output, "Cargo.toml\nsrc/main.rs\n");
+    }
+
+    #[test]
+    fn test_flat_renderer_counts_lines_only_for_loc_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+        let root = Node {
+            name: "test".to_string(),
+            path: dir.path().to_path_buf(),
+            is_dir: true,
+            children: vec![Node::new("main.rs".to_string(), file, false)
+                .with_display_path(PathBuf::from("main.rs"))],
+            ..Default::default()
+        };
+
+        let args = Args::parse_from(["tree2md", "--only-files"]);
+        let ir = FlatRenderer::new(&args).ir_builder.build(&root);
+        assert_eq!(ir.files[0].loc, None);
+
+        let args = Args::parse_from(["tree2md", "--only-files", "--min-loc", "1"]);
+        let ir = FlatRenderer::new(&args).ir_builder.build(&root);
+        assert_eq!(ir.files[0].loc, Some(1));
+    }
+}
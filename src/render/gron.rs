@@ -0,0 +1,124 @@
+use crate::cli::Args;
+use crate::fs_tree::Node;
+use crate::render::pipeline::{IrBuilder, IrDir};
+use crate::render::renderer::{OutputFormat, Renderer};
+
+/// gron-style renderer for `--output gron`.
+/// One assignment per line (`tree.dirs.src.files[0].name = "main.rs";`), so the
+/// tree can be grepped and rebuilt with `gron --ungron`. Files live in a
+/// directory's `files` array and subdirectories under its `dirs` object, so a
+/// directory named `files` cannot clobber the array. `omitted` and `collapsed`
+/// carry the `--max-children-per-dir` and collapsed-directory counts.
+pub struct GronRenderer {
+    ir_builder: IrBuilder,
+}
+
+impl GronRenderer {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            ir_builder: IrBuilder::new(args),
+        }
+    }
+
+    fn render_dir(&self, dir: &IrDir, path: &str, out: &mut String) {
+        out.push_str(&format!("{} = {{}};\n", path));
+        if dir.omitted > 0 {
+            out.push_str(&format!("{}.omitted = {};\n", path, dir.omitted));
+        }
+        if let Some(count) = dir.collapsed_files {
+            out.push_str(&format!("{}.collapsed = {};\n", path, count));
+        }
+
+        if !dir.files.is_empty() {
+            out.push_str(&format!("{}.files = [];\n", path));
+        }
+        for (i, file) in dir.files.iter().enumerate() {
+            let file_path = format!("{}.files[{}]", path, i);
+            out.push_str(&format!("{} = {{}};\n", file_path));
+            out.push_str(&format!(
+                "{}.name = {};\n",
+                file_path,
+                json_string(&file.name)
+            ));
+            out.push_str(&format!("{}.size = {};\n", file_path, file.size_bytes));
+            if let Some(loc) = file.loc {
+                out.push_str(&format!("{}.loc = {};\n", file_path, loc));
+            }
+        }
+
+        if !dir.dirs.is_empty() {
+            out.push_str(&format!("{}.dirs = {{}};\n", path));
+        }
+        let dirs_path = format!("{}.dirs", path);
+        for subdir in &dir.dirs {
+            self.render_dir(subdir, &member(&dirs_path, &subdir.name), out);
+        }
+    }
+}
+
+/// `path.key`, or `path["key"]` when the key is not a plain identifier
+fn member(path: &str, key: &str) -> String {
+    let mut chars = key.chars();
+    let is_ident = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_ident {
+        format!("{}.{}", path, key)
+    } else {
+        format!("{}[{}]", path, json_string(key))
+    }
+}
+
+/// Quote text as a JSON string literal
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Renderer for GronRenderer {
+    fn render_tree(&mut self, root: &Node) -> String {
+        let ir = self.ir_builder.build(root);
+
+        let mut output = String::new();
+        self.render_dir(&ir, "tree", &mut output);
+        output
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Gron
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member() {
+        assert_eq!(member("tree", "src"), "tree.src");
+        assert_eq!(member("tree", "my-dir"), "tree[\"my-dir\"]");
+        assert_eq!(member("tree", ".github"), "tree[\".github\"]");
+        assert_eq!(member("tree", "2024"), "tree[\"2024\"]");
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("main.rs"), "\"main.rs\"");
+        assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(json_string("tab\there"), "\"tab\\there\"");
+    }
+}
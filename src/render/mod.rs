@@ -1,22 +1,48 @@
+pub mod annotations;
+pub mod flat;
+pub mod gron;
 pub mod pipe;
 pub mod pipeline;
 pub mod renderer;
+pub mod rst;
+pub mod table;
 pub mod terminal;
+pub mod toml;
+pub mod xml;
 
+pub use flat::FlatRenderer;
+pub use gron::GronRenderer;
 pub use pipe::PipeRenderer;
 pub use renderer::Renderer;
+pub use rst::RstRenderer;
+pub use table::TableRenderer;
 pub use terminal::TerminalRenderer;
+pub use toml::TomlRenderer;
+pub use xml::XmlRenderer;
 
-use crate::cli::Args;
+use crate::cli::{Args, OutputMode};
 use crate::terminal::capabilities::TerminalCapabilities;
 use crate::terminal::detect::TerminalDetector;
 
-/// Create the appropriate renderer based on TTY detection
+/// Create the appropriate renderer based on `--output` and TTY detection
 pub fn create_renderer<'a>(
     args: &'a Args,
     _capabilities: &TerminalCapabilities,
 ) -> Box<dyn Renderer + 'a> {
-    let detector = TerminalDetector::new();
+    if args.only_files || args.print0 {
+        return Box::new(FlatRenderer::new(args));
+    }
+
+    match args.output {
+        OutputMode::MdTable => return Box::new(TableRenderer::new(args)),
+        OutputMode::Xml => return Box::new(XmlRenderer::new(args)),
+        OutputMode::Gron => return Box::new(GronRenderer::new(args)),
+        OutputMode::Rst => return Box::new(RstRenderer::new(args)),
+        OutputMode::Toml => return Box::new(TomlRenderer::new(args)),
+        _ => {}
+    }
+
+    let detector = TerminalDetector::for_output(args.output);
     let is_tty = detector.is_tty();
 
     if is_tty {
@@ -29,28 +55,11 @@ pub fn create_renderer<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::{FunMode, LocMode, StatsMode};
     use crate::render::renderer::OutputFormat;
+    use clap::Parser;
 
     fn create_test_args() -> Args {
-        Args {
-            target: ".".to_string(),
-            level: None,
-            include: vec![],
-            exclude: vec![],
-            use_gitignore: crate::cli::UseGitignoreMode::Auto,
-            emoji: vec![],
-            emoji_map: None,
-            fun: FunMode::Off,
-            no_anim: false,
-            stats: StatsMode::Off,
-            loc: LocMode::Off,
-            contents: false,
-            max_chars: None,
-            contents_mode: crate::cli::ContentsMode::Head,
-            safe: true,
-            unsafe_mode: false,
-        }
+        Args::parse_from(["tree2md", "--fun", "off", "--stats", "off", "--loc", "off"])
     }
 
     #[test]
@@ -62,4 +71,17 @@ mod tests {
 
         assert_eq!(renderer.output_format(), OutputFormat::Pipe);
     }
+
+    #[test]
+    fn test_create_renderer_forced_output() {
+        let capabilities = TerminalCapabilities::new();
+
+        let args = Args::parse_from(["tree2md", "--output", "tty"]);
+        let renderer = create_renderer(&args, &capabilities);
+        assert_eq!(renderer.output_format(), OutputFormat::Terminal);
+
+        let args = Args::parse_from(["tree2md", "--output", "pipe"]);
+        let renderer = create_renderer(&args, &capabilities);
+        assert_eq!(renderer.output_format(), OutputFormat::Pipe);
+    }
 }
@@ -1,5 +1,5 @@
-use crate::cli::{Args, ContentsMode};
-use crate::content::io::is_binary_extension;
+use crate::cli::{Args, ContentEncoding, ContentsMode};
+use crate::content::io::{is_binary_extension, read_text};
 use crate::content::truncate::{
     collapse_at_indent, find_head_n, find_nest_threshold, truncate_head_lines,
 };
@@ -7,8 +7,14 @@ use crate::fs_tree::{LocCounter, Node};
 use crate::language::detect_lang;
 use crate::output::stats::Stats;
 use crate::profile::EmojiMapper;
-use crate::render::pipeline::{build_ir, AggregationContext, IrDir, IrFile};
+use crate::render::pipeline::{
+    build_ir, collect_files, root_name, AggregationContext, IrDir, IrEntry, IrFile, IrOptions,
+};
 use crate::render::renderer::{OutputFormat, Renderer};
+use crate::util::format::truncate_name;
+use globset::{Glob, GlobMatcher};
+use std::collections::HashSet;
+use std::path::PathBuf;
 
 /// Pipe renderer for non-TTY output.
 /// Produces plain tree characters with optional line counts and file contents.
@@ -17,7 +23,12 @@ pub struct PipeRenderer<'a> {
     emoji_mapper: EmojiMapper,
     stats: Stats,
     loc_counter: LocCounter,
+    ir_options: IrOptions,
     output: String,
+    /// `--contents-lang` overrides, matched against file names in order
+    lang_overrides: Vec<(GlobMatcher, String)>,
+    /// Files that were not valid UTF-8 and were decoded per `--encoding`
+    transcoded: HashSet<PathBuf>,
 }
 
 impl<'a> PipeRenderer<'a> {
@@ -25,46 +36,123 @@ impl<'a> PipeRenderer<'a> {
         Self {
             args,
             emoji_mapper: EmojiMapper::new(false), // no emoji in pipe mode
-            stats: Stats::new(),
+            stats: Stats::from_args(args),
             loc_counter: LocCounter::new(args.loc.clone()),
+            ir_options: IrOptions::from_args(args),
             output: String::new(),
+            lang_overrides: compile_lang_overrides(&args.contents_lang),
+            transcoded: HashSet::new(),
+        }
+    }
+
+    /// Text of a file for `-c`, or `None` for binaries, symlinks and files
+    /// that can't be read under `--encoding`
+    fn read_content(&mut self, file: &IrFile) -> Option<String> {
+        if is_binary_extension(&file.path) || file.symlink_target.is_some() {
+            return None;
         }
+        let (text, transcoded) = read_text(&file.path, self.args.encoding)?;
+        if transcoded {
+            self.transcoded.insert(file.path.clone());
+        }
+        Some(text)
     }
 
-    fn render_ir_dir(&mut self, dir: &IrDir, prefix: &str) {
-        let total = dir.dirs.len() + dir.files.len();
-        let mut idx = 0;
+    /// Fence language for a file: `--contents-lang` overrides first, then detection
+    fn fence_lang(&self, file_name: &str) -> &str {
+        self.lang_overrides
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(file_name))
+            .map(|(_, lang)| lang.as_str())
+            .or_else(|| detect_lang(file_name).map(|l| l.name))
+            .unwrap_or("")
+    }
 
-        // Render subdirectories first
-        for subdir in &dir.dirs {
-            idx += 1;
-            let is_last = idx == total;
-            let branch = if is_last { "└── " } else { "├── " };
-            let continuation = if is_last { "    " } else { "│   " };
+    /// Render the children of `dir`, which sit at nesting `depth`
+    fn render_ir_dir(&mut self, dir: &IrDir, prefix: &str, depth: usize) {
+        let entries = dir.entries(self.ir_options.dirs_last);
+        let total = entries.len();
 
+        for (i, entry) in entries.into_iter().enumerate() {
+            let is_last = i + 1 == total && dir.omitted == 0;
+            match entry {
+                IrEntry::Dir(subdir) => self.render_ir_subdir(subdir, prefix, depth, is_last),
+                IrEntry::File(file) => self.render_ir_file(file, prefix, depth, is_last),
+            }
+        }
+
+        if dir.omitted > 0 {
             self.output
-                .push_str(&format!("{}{}{}/\n", prefix, branch, subdir.name));
+                .push_str(&format!("{}└── … {} more\n", prefix, dir.omitted));
+        }
+    }
+
+    fn render_ir_subdir(&mut self, subdir: &IrDir, prefix: &str, depth: usize, is_last: bool) {
+        let branch = if is_last { "└── " } else { "├── " };
+        let continuation = if is_last { "    " } else { "│   " };
 
-            let new_prefix = format!("{}{}", prefix, continuation);
-            self.render_ir_dir(subdir, &new_prefix);
+        self.output.push_str(&format!(
+            "{}{}{}{}{}",
+            prefix,
+            branch,
+            self.args.depth_label(depth),
+            truncate_name(&subdir.name, self.args.max_name_length),
+            self.args.dir_suffix()
+        ));
+        if subdir.ignored {
+            self.output.push_str("  (ignored)");
+        }
+        if let Some(count) = subdir.collapsed_files {
+            self.output
+                .push_str(&format!("  ({} files, collapsed)", count));
+        }
+        if let Some(annotation) = &subdir.annotation {
+            self.output.push_str(&format!("  — {}", annotation));
         }
+        self.output.push('\n');
 
-        // Then render files
-        for file in &dir.files {
-            idx += 1;
-            let is_last = idx == total;
-            let branch = if is_last { "└── " } else { "├── " };
+        let new_prefix = format!("{}{}", prefix, continuation);
+        if self.args.shows_dir_subtotal(subdir.totals().0) {
+            self.output
+                .push_str(&format!("{}{}\n", new_prefix, subdir.totals_label()));
+        }
+        self.render_ir_dir(subdir, &new_prefix, depth + 1);
+    }
 
-            self.output.push_str(prefix);
-            self.output.push_str(branch);
-            self.output.push_str(&file.name);
+    fn render_ir_file(&mut self, file: &IrFile, prefix: &str, depth: usize, is_last: bool) {
+        let branch = if is_last { "└── " } else { "├── " };
 
-            if let Some(loc) = file.loc {
-                self.output.push_str(&format!("  ({} lines)", loc));
-            }
+        self.output.push_str(prefix);
+        self.output.push_str(branch);
+        self.output.push_str(&self.args.depth_label(depth));
+        if file.highlighted {
+            self.output
+                .push_str(&format!("**{}**", file.display_name(self.args)));
+        } else {
+            self.output.push_str(&file.display_name(self.args));
+        }
 
-            self.output.push('\n');
+        if let Some(loc) = file.loc {
+            self.output.push_str(&format!("  ({} lines)", loc));
+        }
+
+        if file.loc_skipped {
+            self.output.push_str("  (too large to count)");
         }
+
+        if file.generated {
+            self.output.push_str("  (generated)");
+        }
+
+        if file.ignored {
+            self.output.push_str("  (ignored)");
+        }
+
+        if let Some(annotation) = &file.annotation {
+            self.output.push_str(&format!("  — {}", annotation));
+        }
+
+        self.output.push('\n');
     }
 
     fn render_contents(&mut self, dir: &IrDir) {
@@ -88,26 +176,17 @@ impl<'a> PipeRenderer<'a> {
         let files = collect_files(dir);
 
         // Read all file contents
-        let contents: Vec<Option<String>> = files
-            .iter()
-            .map(|f| {
-                if is_binary_extension(&f.path) {
-                    None
-                } else {
-                    std::fs::read_to_string(&f.path).ok()
-                }
-            })
-            .collect();
+        let contents: Vec<Option<String>> = files.iter().map(|f| self.read_content(f)).collect();
 
-        // Check if total fits within budget
+        // Check if total fits within budget (counted in chars, not bytes)
         let total_chars: usize = contents
             .iter()
-            .map(|c| c.as_ref().map_or(0, |s| s.len()))
+            .map(|c| c.as_ref().map_or(0, |s| s.chars().count()))
             .sum();
         if total_chars <= max_chars {
             for (file, content) in files.iter().zip(contents.iter()) {
                 if let Some(content) = content {
-                    self.emit_file_section(file, content, 0);
+                    self.emit_file_section(file, content, 0, 0);
                 }
             }
             return;
@@ -122,7 +201,7 @@ impl<'a> PipeRenderer<'a> {
                 for (file, content) in files.iter().zip(contents.iter()) {
                     if let Some(content) = content {
                         let (truncated, omitted) = truncate_head_lines(content, n);
-                        self.emit_file_section(file, &truncated, omitted);
+                        self.emit_file_section(file, &truncated, omitted, content.lines().count());
                     }
                 }
             }
@@ -134,7 +213,7 @@ impl<'a> PipeRenderer<'a> {
                             if let Some(content) = content {
                                 let lines: Vec<&str> = content.lines().collect();
                                 let (collapsed, omitted) = collapse_at_indent(&lines, t);
-                                self.emit_file_section(file, &collapsed, omitted);
+                                self.emit_file_section(file, &collapsed, omitted, lines.len());
                             }
                         }
                     }
@@ -144,7 +223,12 @@ impl<'a> PipeRenderer<'a> {
                         for (file, content) in files.iter().zip(contents.iter()) {
                             if let Some(content) = content {
                                 let (truncated, omitted) = truncate_head_lines(content, n);
-                                self.emit_file_section(file, &truncated, omitted);
+                                self.emit_file_section(
+                                    file,
+                                    &truncated,
+                                    omitted,
+                                    content.lines().count(),
+                                );
                             }
                         }
                     }
@@ -154,59 +238,81 @@ impl<'a> PipeRenderer<'a> {
     }
 
     fn render_file_content(&mut self, file: &IrFile, _max_chars: Option<usize>) {
-        if is_binary_extension(&file.path) {
-            return;
-        }
-        if let Ok(content) = std::fs::read_to_string(&file.path) {
-            self.emit_file_section(file, &content, 0);
+        if let Some(content) = self.read_content(file) {
+            self.emit_file_section(file, &content, 0, 0);
         }
     }
 
-    fn emit_file_section(&mut self, file: &IrFile, content: &str, omitted_lines: usize) {
+    fn emit_file_section(
+        &mut self,
+        file: &IrFile,
+        content: &str,
+        omitted_lines: usize,
+        total_lines: usize,
+    ) {
         let file_name = file
             .path
             .file_name()
             .unwrap_or_default()
             .to_string_lossy()
             .to_string();
-        let lang_hint = detect_lang(&file_name).map(|l| l.name).unwrap_or("");
-
-        self.output.push_str(&format!(
-            "\n## {}\n\n```{}\n",
-            file.display_path.display(),
-            lang_hint
-        ));
+        let lang_hint = self.fence_lang(&file_name).to_string();
+
+        self.output
+            .push_str(&format!("\n## {}\n\n", file.display_path.display()));
+        if self.transcoded.contains(&file.path) {
+            let encoding = match self.args.encoding {
+                ContentEncoding::Latin1 => "Latin-1",
+                _ => "UTF-8 with replacement characters",
+            };
+            self.output
+                .push_str(&format!("(not valid UTF-8; decoded as {})\n\n", encoding));
+        }
+        self.output.push_str(&format!("```{}\n", lang_hint));
         self.output.push_str(content);
         if !content.ends_with('\n') {
             self.output.push('\n');
         }
         if omitted_lines > 0 {
-            self.output
-                .push_str(&format!("... ({} lines omitted)\n", omitted_lines));
+            let note = format_truncation_note(
+                &self.args.truncation_note,
+                total_lines.saturating_sub(omitted_lines),
+                total_lines,
+                omitted_lines,
+            );
+            self.output.push_str(&note);
+            self.output.push('\n');
         }
         self.output.push_str("```\n");
     }
 }
 
-/// Collect all files in DFS order from an IrDir tree.
-fn collect_files(dir: &IrDir) -> Vec<&IrFile> {
-    let mut result = Vec::new();
-    collect_files_rec(dir, &mut result);
-    result
+/// Compile `GLOB=LANG` pairs; invalid globs are reported and skipped
+fn compile_lang_overrides(pairs: &[(String, String)]) -> Vec<(GlobMatcher, String)> {
+    pairs
+        .iter()
+        .filter_map(|(glob, lang)| match Glob::new(glob) {
+            Ok(g) => Some((g.compile_matcher(), lang.clone())),
+            Err(e) => {
+                eprintln!("Warning: Invalid --contents-lang pattern '{}': {}", glob, e);
+                None
+            }
+        })
+        .collect()
 }
 
-fn collect_files_rec<'a>(dir: &'a IrDir, out: &mut Vec<&'a IrFile>) {
-    for subdir in &dir.dirs {
-        collect_files_rec(subdir, out);
-    }
-    for file in &dir.files {
-        out.push(file);
-    }
+/// Substitute `{shown}`, `{total}` and `{omitted}` line counts into a truncation note
+fn format_truncation_note(template: &str, shown: usize, total: usize, omitted: usize) -> String {
+    template
+        .replace("{shown}", &shown.to_string())
+        .replace("{total}", &total.to_string())
+        .replace("{omitted}", &omitted.to_string())
 }
 
 impl<'a> Renderer for PipeRenderer<'a> {
     fn render_tree(&mut self, root: &Node) -> String {
         self.output.clear();
+        self.transcoded.clear();
         self.stats.reset();
 
         if !root.children.is_empty() {
@@ -217,16 +323,40 @@ impl<'a> Renderer for PipeRenderer<'a> {
             emoji_mapper: &self.emoji_mapper,
             stats: &mut self.stats,
             loc_counter: &self.loc_counter,
+            options: &self.ir_options,
         };
 
         let ir = build_ir(root, &mut ctx);
 
+        // Optional heading with the root directory name
+        if let Some(level) = self.args.render_root_as_heading {
+            let icon = self
+                .args
+                .root_emoji
+                .as_ref()
+                .map(|glyph| format!("{} ", glyph))
+                .unwrap_or_default();
+            self.output.push_str(&format!(
+                "{} {}{}\n\n",
+                "#".repeat(level as usize),
+                icon,
+                root_name(root)
+            ));
+        }
+
         // Render tree structure
-        self.output.push_str(".\n");
-        self.render_ir_dir(&ir, "");
+        // The scan root (empty display path) is `.`; a renamed root or a
+        // subtree rendered on its own (--split-output) shows its display path
+        let root_line = if root.display_path.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            root.display_path.to_string_lossy().replace('\\', "/")
+        };
+        self.output.push_str(&format!("{}\n", root_line));
+        self.render_ir_dir(&ir, "", 0);
 
         // Append stats if enabled
-        if self.args.should_show_stats() {
+        if self.args.should_show_stats(self.stats.total_files()) {
             self.output.push('\n');
             self.output.push_str(&self.render_stats(&self.stats));
         }
@@ -251,29 +381,12 @@ impl<'a> Renderer for PipeRenderer<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::{ContentsMode, FunMode, LocMode, StatsMode};
     use crate::fs_tree::Node;
+    use clap::Parser;
     use std::path::PathBuf;
 
     fn create_test_args() -> Args {
-        Args {
-            target: ".".to_string(),
-            level: None,
-            include: vec![],
-            exclude: vec![],
-            use_gitignore: crate::cli::UseGitignoreMode::Auto,
-            emoji: vec![],
-            emoji_map: None,
-            fun: FunMode::Off,
-            no_anim: false,
-            stats: StatsMode::Off,
-            loc: LocMode::Off,
-            contents: false,
-            max_chars: None,
-            contents_mode: ContentsMode::Head,
-            safe: true,
-            unsafe_mode: false,
-        }
+        Args::parse_from(["tree2md", "--fun", "off", "--stats", "off", "--loc", "off"])
     }
 
     #[test]
@@ -298,7 +411,9 @@ mod tests {
                         is_dir: false,
                         display_path: PathBuf::from("src/main.rs"),
                         children: vec![],
+                        ..Default::default()
                     }],
+                    ..Default::default()
                 },
                 Node {
                     name: "Cargo.toml".to_string(),
@@ -306,8 +421,10 @@ mod tests {
                     is_dir: false,
                     display_path: PathBuf::from("Cargo.toml"),
                     children: vec![],
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = renderer.render_tree(&root);
@@ -318,6 +435,18 @@ mod tests {
         assert!(output.contains("├── ") || output.contains("└── "));
     }
 
+    #[test]
+    fn test_format_truncation_note() {
+        assert_eq!(
+            format_truncation_note("... ({omitted} lines omitted)", 2, 5, 3),
+            "... (3 lines omitted)"
+        );
+        assert_eq!(
+            format_truncation_note("// showing {shown} of {total}", 2, 5, 3),
+            "// showing 2 of 5"
+        );
+    }
+
     #[test]
     fn test_pipe_renderer_output_format() {
         let args = create_test_args();
@@ -1,7 +1,23 @@
+use crate::cli::{Args, LocMode};
 use crate::fs_tree::{LocCounter, Node};
+use crate::matcher::MatchSpec;
 use crate::output::stats::Stats;
 use crate::profile::{EmojiMapper, FileType};
-use std::path::PathBuf;
+use crate::render::annotations::Annotations;
+use crate::util::format::truncate_name;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Globs treated as generated files when `--dim-generated` is given without `--generated-glob`
+const DEFAULT_GENERATED_GLOBS: &[&str] = &[
+    "*.lock",
+    "package-lock.json",
+    "*.min.js",
+    "*.min.css",
+    "*.map",
+    "dist/**",
+];
 
 /// Intermediate representation for a file
 #[derive(Debug, Clone)]
@@ -10,21 +26,156 @@ pub struct IrFile {
     /// Actual filesystem path (for reading file contents)
     pub path: PathBuf,
     pub display_path: PathBuf,
-    #[allow(dead_code)]
     pub file_type: FileType,
     pub emoji: String,
     pub loc: Option<usize>,
-    #[allow(dead_code)]
     pub size_bytes: u64,
+    /// Over `--max-file-size-for-loc`, so lines were not counted
+    pub loc_skipped: bool,
+    /// Matched a `--generated-glob` pattern (rendered de-emphasized)
+    pub generated: bool,
+    /// Matched a `--highlight` pattern (rendered emphasized)
+    pub highlighted: bool,
+    /// Gitignored, kept by `--include-gitignored`
+    pub ignored: bool,
+    /// Target of a symlink listed by `--show-symlinks`
+    pub symlink_target: Option<PathBuf>,
+    /// Has an execute bit set (unix only)
+    pub executable: bool,
+    /// Description from `--annotate-from`
+    pub annotation: Option<String>,
 }
 
 /// Intermediate representation for a directory
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct IrDir {
     pub name: String,
     pub display_path: PathBuf,
     pub files: Vec<IrFile>,
     pub dirs: Vec<IrDir>,
+    /// Gitignored, kept by `--include-gitignored` (contents not walked)
+    pub ignored: bool,
+    /// Children dropped by `--max-children-per-dir`
+    pub omitted: usize,
+    /// File count of a directory collapsed by `--prune-dirs-over` or `--summarize-known-dirs`
+    pub collapsed_files: Option<usize>,
+    /// Description from `--annotate-from`
+    pub annotation: Option<String>,
+}
+
+/// Options that shape the IR while it is being built
+#[derive(Debug, Clone, Default)]
+pub struct IrOptions {
+    /// Drop all files, keeping only the directory skeleton
+    pub only_dirs: bool,
+    /// Files matching this set are marked as generated
+    pub generated: Option<GlobSet>,
+    /// Files matching this set are marked as highlighted
+    pub highlight: Option<GlobSet>,
+    /// List files before subdirectories
+    pub dirs_last: bool,
+    /// Path descriptions appended to entries
+    pub annotations: Option<Annotations>,
+    /// Files larger than this many bytes are not read for LOC
+    pub max_loc_size: Option<u64>,
+    /// Keep at most this many children per directory (alphabetically first)
+    pub max_children: Option<usize>,
+    /// Nest this many levels, then list deeper files by relative path
+    pub flatten_depth: Option<usize>,
+    /// Drop files whose counted LOC is 0
+    pub exclude_zero_loc: bool,
+    /// Drop files with fewer counted lines
+    pub min_loc: Option<usize>,
+    /// Drop files with more counted lines
+    pub max_loc: Option<usize>,
+    /// Gather test files into a virtual `tests (N)` directory
+    pub group_tests: bool,
+}
+
+impl IrOptions {
+    /// Create IrOptions from CLI arguments
+    pub fn from_args(args: &Args) -> Self {
+        let case_sensitive = MatchSpec::globs_case_sensitive(args);
+        let generated = if !args.dim_generated {
+            None
+        } else if args.generated_glob.is_empty() {
+            let defaults: Vec<String> = DEFAULT_GENERATED_GLOBS
+                .iter()
+                .map(|g| g.to_string())
+                .collect();
+            build_globset(&defaults, case_sensitive)
+        } else {
+            build_globset(&args.generated_glob, case_sensitive)
+        };
+
+        Self {
+            only_dirs: args.only_dirs,
+            generated,
+            highlight: if args.highlight.is_empty() {
+                None
+            } else {
+                build_globset(&args.highlight, case_sensitive)
+            },
+            dirs_last: args.group_dirs_last,
+            max_loc_size: if args.loc == LocMode::Off {
+                None
+            } else {
+                args.max_file_size_for_loc
+            },
+            max_children: args.max_children_per_dir,
+            flatten_depth: args.flatten_depth,
+            exclude_zero_loc: args.exclude_zero_loc,
+            min_loc: args.min_loc,
+            max_loc: args.max_loc,
+            group_tests: args.group_tests,
+            annotations: args.annotations.clone(),
+        }
+    }
+
+    /// Whether any option drops files by their line count
+    pub fn filters_loc(&self) -> bool {
+        self.exclude_zero_loc || self.min_loc.is_some() || self.max_loc.is_some()
+    }
+
+    /// Whether a file with this counted LOC is dropped; files without a
+    /// count (LOC off, binary, too large) always stay
+    fn drops_loc(&self, loc: Option<usize>) -> bool {
+        loc.is_some_and(|loc| {
+            (self.exclude_zero_loc && loc == 0)
+                || self.min_loc.is_some_and(|min| loc < min)
+                || self.max_loc.is_some_and(|max| loc > max)
+        })
+    }
+
+    fn annotation_for(&self, display_path: &Path) -> Option<String> {
+        self.annotations
+            .as_ref()
+            .and_then(|a| a.lookup(display_path))
+            .map(str::to_string)
+    }
+}
+
+/// Compile display-path globs, normalized and case-folded like `-I`/`-X`
+/// patterns. Invalid patterns are reported and skipped.
+fn build_globset(patterns: &[String], case_sensitive: bool) -> Option<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(&MatchSpec::normalize_pattern(pattern))
+            .case_insensitive(!case_sensitive)
+            .build();
+        match glob {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => eprintln!("Warning: Invalid glob pattern '{}': {}", pattern, e),
+        }
+    }
+    builder.build().ok()
+}
+
+/// Display path as a forward-slash string for glob matching
+fn match_str(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
 }
 
 /// Context for aggregation during IR building
@@ -32,27 +183,198 @@ pub struct AggregationContext<'a> {
     pub emoji_mapper: &'a EmojiMapper,
     pub stats: &'a mut Stats,
     pub loc_counter: &'a LocCounter,
+    pub options: &'a IrOptions,
+}
+
+/// IR building for renderers whose output is the IR alone (table, xml, gron,
+/// rst, toml, flat): no emoji, and the stats they gather are never printed
+pub struct IrBuilder {
+    emoji_mapper: EmojiMapper,
+    stats: Stats,
+    loc_counter: LocCounter,
+    options: IrOptions,
+}
+
+impl IrBuilder {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            emoji_mapper: EmojiMapper::new(false),
+            stats: Stats::new(),
+            loc_counter: LocCounter::new(args.loc.clone()),
+            options: IrOptions::from_args(args),
+        }
+    }
+
+    /// Count lines with `mode` instead of `--loc`
+    pub fn with_loc_mode(mut self, mode: LocMode) -> Self {
+        self.loc_counter = LocCounter::new(mode);
+        self
+    }
+
+    pub fn options(&self) -> &IrOptions {
+        &self.options
+    }
+
+    /// Build the IR for `root`
+    pub fn build(&mut self, root: &Node) -> IrDir {
+        self.stats.reset();
+
+        let mut ctx = AggregationContext {
+            emoji_mapper: &self.emoji_mapper,
+            stats: &mut self.stats,
+            loc_counter: &self.loc_counter,
+            options: &self.options,
+        };
+        build_ir(root, &mut ctx)
+    }
+}
+
+/// Name shown for a tree's root: the directory's own name even when scanned
+/// as `.`, or `name` once `--rename-root` (or a subtree) gave it a display path
+pub fn root_name(root: &Node) -> String {
+    if !root.display_path.as_os_str().is_empty() {
+        return root.name.clone();
+    }
+    root.path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.name.clone())
+}
+
+/// Stats for `root` as the renderers count them, without rendering
+pub fn collect_stats(root: &Node, args: &Args) -> Stats {
+    let mut stats = Stats::from_args(args);
+    if !root.children.is_empty() {
+        stats.add_directory();
+    }
+
+    let emoji_mapper = EmojiMapper::new(false);
+    let loc_counter = LocCounter::new(args.loc.clone());
+    let options = IrOptions::from_args(args);
+    let mut ctx = AggregationContext {
+        emoji_mapper: &emoji_mapper,
+        stats: &mut stats,
+        loc_counter: &loc_counter,
+        options: &options,
+    };
+    build_ir(root, &mut ctx);
+    stats
 }
 
 /// Build the intermediate representation from the filesystem tree
 pub fn build_ir(root: &Node, ctx: &mut AggregationContext) -> IrDir {
-    build_ir_node(root, ctx)
+    let mut ir = build_ir_node(root, 0, ctx);
+    if let Some(depth) = ctx.options.flatten_depth {
+        flatten_below(&mut ir, depth);
+    }
+    if ctx.options.group_tests {
+        group_tests(&mut ir);
+    }
+    ir
+}
+
+/// `--group-tests`: move each directory's test files into a virtual
+/// `tests (N)` child listed after the real subdirectories. It shares the
+/// parent's display path and is not counted as a directory in stats.
+fn group_tests(dir: &mut IrDir) {
+    for subdir in &mut dir.dirs {
+        group_tests(subdir);
+    }
+
+    let (tests, files): (Vec<IrFile>, Vec<IrFile>) = std::mem::take(&mut dir.files)
+        .into_iter()
+        .partition(|f| f.file_type == FileType::Test);
+    dir.files = files;
+    if tests.is_empty() {
+        return;
+    }
+
+    dir.dirs.push(IrDir {
+        name: format!("tests ({})", tests.len()),
+        display_path: dir.display_path.clone(),
+        files: tests,
+        dirs: Vec::new(),
+        ignored: false,
+        omitted: 0,
+        collapsed_files: None,
+        annotation: None,
+    });
+}
+
+/// `--flatten-depth`: keep `depth` levels of nesting, then replace each
+/// deeper subtree with its files, named by path (`util/mod.rs`) and sorted
+fn flatten_below(dir: &mut IrDir, depth: usize) {
+    if depth > 0 {
+        for subdir in &mut dir.dirs {
+            flatten_below(subdir, depth - 1);
+        }
+        return;
+    }
+
+    for subdir in std::mem::take(&mut dir.dirs) {
+        drain_files(subdir, "", &mut dir.files);
+    }
+    dir.files.sort_by(|a, b| a.name.cmp(&b.name));
+}
+
+/// Move every file under `dir` into `out`, prefixing names with their path
+fn drain_files(dir: IrDir, prefix: &str, out: &mut Vec<IrFile>) {
+    let prefix = format!("{}{}/", prefix, dir.name);
+    for mut file in dir.files {
+        file.name = format!("{}{}", prefix, file.name);
+        out.push(file);
+    }
+    for subdir in dir.dirs {
+        drain_files(subdir, &prefix, out);
+    }
 }
 
-fn build_ir_node(node: &Node, ctx: &mut AggregationContext) -> IrDir {
+/// Build one directory; `depth` is the depth of its children (0 under the root)
+fn build_ir_node(node: &Node, depth: usize, ctx: &mut AggregationContext) -> IrDir {
     let mut files = Vec::new();
     let mut dirs = Vec::new();
 
+    // With --max-children-per-dir, keep the alphabetically-first names so the
+    // selection doesn't depend on the dirs-first/last display order
+    let kept: Option<HashSet<&str>> = ctx
+        .options
+        .max_children
+        .filter(|&max| node.children.len() > max)
+        .map(|max| {
+            let mut names: Vec<&str> = node.children.iter().map(|c| c.name.as_str()).collect();
+            names.sort_unstable();
+            names.into_iter().take(max).collect()
+        });
+    let omitted = kept
+        .as_ref()
+        .map_or(0, |kept| node.children.len() - kept.len());
+
     // Process children
     for child in &node.children {
+        if kept
+            .as_ref()
+            .is_some_and(|kept| !kept.contains(child.name.as_str()))
+        {
+            continue;
+        }
+
         if child.is_dir {
+            // Recursively build IR for subdirectory
+            let ir_dir = build_ir_node(child, depth + 1, ctx);
+
+            // --exclude-zero-loc/--min-loc/--max-loc: drop directories they emptied
+            if ctx.options.filters_loc()
+                && ir_dir.is_empty()
+                && ir_dir.collapsed_files.is_none()
+                && child.file_count() > 0
+            {
+                continue;
+            }
+
             // Add directory to stats
             ctx.stats.add_directory();
-
-            // Recursively build IR for subdirectory
-            let ir_dir = build_ir_node(child, ctx);
             dirs.push(ir_dir);
-        } else {
+        } else if !ctx.options.only_dirs {
             // Classify file type
             let file_type = FileType::classify_path(&child.path);
 
@@ -62,22 +384,37 @@ fn build_ir_node(node: &Node, ctx: &mut AggregationContext) -> IrDir {
                 .get_emoji(&child.path, file_type)
                 .to_string();
 
+            // Count lines of code if enabled (symlinks are never read through,
+            // and files over --max-file-size-for-loc are never opened)
+            let loc_skipped = ctx.options.max_loc_size.is_some_and(|max| child.size > max);
+            let loc = if child.symlink_target.is_some() || loc_skipped {
+                None
+            } else {
+                ctx.loc_counter.count_lines(&child.path)
+            };
+
+            // Files filtered by line count never reach stats or output
+            if ctx.options.drops_loc(loc) {
+                continue;
+            }
+
             // Add file to stats
             ctx.stats.add_file(file_type, emoji.clone(), &child.path);
-
-            // Count lines of code if enabled
-            let loc = if let Some(line_count) = ctx.loc_counter.count_lines(&child.path) {
+            ctx.stats.add_file_depth(depth);
+            if let Some(line_count) = loc {
                 ctx.stats.add_loc(file_type, line_count);
-                Some(line_count)
-            } else {
-                None
-            };
+            }
 
-            // Get file size
-            let size_bytes = std::fs::metadata(&child.path)
-                .ok()
-                .map(|m| m.len())
-                .unwrap_or(0);
+            let generated = ctx
+                .options
+                .generated
+                .as_ref()
+                .is_some_and(|set| set.is_match(match_str(&child.display_path)));
+            let highlighted = ctx
+                .options
+                .highlight
+                .as_ref()
+                .is_some_and(|set| set.is_match(match_str(&child.display_path)));
 
             // Create IR file
             let ir_file = IrFile {
@@ -87,7 +424,14 @@ fn build_ir_node(node: &Node, ctx: &mut AggregationContext) -> IrDir {
                 file_type,
                 emoji,
                 loc,
-                size_bytes,
+                size_bytes: child.size,
+                loc_skipped,
+                generated,
+                highlighted,
+                ignored: child.ignored,
+                symlink_target: child.symlink_target.clone(),
+                executable: child.executable,
+                annotation: ctx.options.annotation_for(&child.display_path),
             };
 
             files.push(ir_file);
@@ -102,11 +446,107 @@ fn build_ir_node(node: &Node, ctx: &mut AggregationContext) -> IrDir {
         display_path: node.display_path.clone(),
         files,
         dirs,
+        ignored: node.ignored,
+        omitted,
+        collapsed_files: node.collapsed_files,
+        annotation: ctx.options.annotation_for(&node.display_path),
+    }
+}
+
+/// Collect all files in DFS order from an IrDir tree.
+pub fn collect_files(dir: &IrDir) -> Vec<&IrFile> {
+    let mut result = Vec::new();
+    collect_files_rec(dir, &mut result);
+    result
+}
+
+fn collect_files_rec<'a>(dir: &'a IrDir, out: &mut Vec<&'a IrFile>) {
+    for subdir in &dir.dirs {
+        collect_files_rec(subdir, out);
+    }
+    for file in &dir.files {
+        out.push(file);
     }
 }
 
+impl IrFile {
+    /// Name as rendered in a tree: shortened by `--max-name-length`,
+    /// suffixed by `--classify`, and `link -> target` for symlinks
+    pub fn display_name(&self, args: &Args) -> String {
+        // --strip-extension only changes the label; paths keep the full name
+        let label = if args.strip_extension {
+            Path::new(&self.name)
+                .with_extension("")
+                .to_string_lossy()
+                .into_owned()
+        } else {
+            self.name.clone()
+        };
+        let mut name = truncate_name(&label, args.max_name_length);
+        if args.classify {
+            name.push_str(self.classify_suffix());
+        }
+        match &self.symlink_target {
+            Some(target) => format!("{} -> {}", name, target.display()),
+            None => name,
+        }
+    }
+
+    /// `ls -F` style type indicator: `@` for symlinks, `*` for executables
+    pub fn classify_suffix(&self) -> &'static str {
+        if self.symlink_target.is_some() {
+            "@"
+        } else if self.executable {
+            "*"
+        } else {
+            ""
+        }
+    }
+}
+
+/// A child of an `IrDir` in display order
+pub enum IrEntry<'a> {
+    Dir(&'a IrDir),
+    File(&'a IrFile),
+}
+
 /// Extension methods for IR nodes to simplify rendering
 impl IrDir {
+    /// Children in display order: directories first, or last with `dirs_last`
+    pub fn entries(&self, dirs_last: bool) -> Vec<IrEntry<'_>> {
+        let dirs = self.dirs.iter().map(IrEntry::Dir);
+        let files = self.files.iter().map(IrEntry::File);
+        if dirs_last {
+            files.chain(dirs).collect()
+        } else {
+            dirs.chain(files).collect()
+        }
+    }
+
+    /// Files and lines of code in this directory and everything below it
+    /// (`None` lines when nothing was counted)
+    pub fn totals(&self) -> (usize, Option<usize>) {
+        let mut files = self.files.len();
+        let mut loc = self.files.iter().filter_map(|f| f.loc).reduce(|a, b| a + b);
+        for dir in &self.dirs {
+            let (sub_files, sub_loc) = dir.totals();
+            files += sub_files;
+            loc = match (loc, sub_loc) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            };
+        }
+        (files, loc)
+    }
+
+    /// `(3 files, 120 lines)` subtotal shown under `--aggregate-by dir`
+    pub fn totals_label(&self) -> String {
+        match self.totals() {
+            (files, Some(loc)) => format!("({} files, {} lines)", files, loc),
+            (files, None) => format!("({} files)", files),
+        }
+    }
+
     /// Get total count of immediate children (files and directories)
     #[allow(dead_code)]
     pub fn immediate_child_count(&self) -> (usize, usize) {
@@ -114,7 +554,6 @@ impl IrDir {
     }
 
     /// Check if directory is empty
-    #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
         self.files.is_empty() && self.dirs.is_empty()
     }
@@ -144,7 +583,9 @@ mod tests {
                         is_dir: false,
                         display_path: PathBuf::from("src/main.rs"),
                         children: vec![],
+                        ..Default::default()
                     }],
+                    ..Default::default()
                 },
                 Node {
                     name: "README.md".to_string(),
@@ -152,8 +593,10 @@ mod tests {
                     is_dir: false,
                     display_path: PathBuf::from("README.md"),
                     children: vec![],
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         }
     }
 
@@ -163,11 +606,13 @@ mod tests {
         let emoji_mapper = EmojiMapper::new(false);
         let mut stats = Stats::new();
         let loc_counter = LocCounter::new(LocMode::Off);
+        let options = IrOptions::default();
 
         let mut ctx = AggregationContext {
             emoji_mapper: &emoji_mapper,
             stats: &mut stats,
             loc_counter: &loc_counter,
+            options: &options,
         };
 
         let ir = build_ir(&root, &mut ctx);
@@ -182,6 +627,125 @@ mod tests {
         assert_eq!(src_dir.files[0].name, "main.rs");
 
         assert_eq!(ir.files[0].name, "README.md");
+
+        // README.md at the root, main.rs one level down
+        assert_eq!(stats.depth_counts(), &[1, 1]);
+    }
+
+    #[test]
+    fn test_ir_dir_totals() {
+        let file = |name: &str, loc: Option<usize>| IrFile {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            display_path: PathBuf::from(name),
+            file_type: FileType::Text,
+            emoji: String::new(),
+            loc,
+            size_bytes: 0,
+            loc_skipped: false,
+            generated: false,
+            highlighted: false,
+            ignored: false,
+            symlink_target: None,
+            executable: false,
+            annotation: None,
+        };
+        let dir = IrDir {
+            files: vec![file("a.rs", Some(10)), file("b.bin", None)],
+            dirs: vec![IrDir {
+                files: vec![file("c.rs", Some(5))],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(dir.totals(), (3, Some(15)));
+        assert_eq!(dir.totals_label(), "(3 files, 15 lines)");
+        assert_eq!(IrDir::default().totals_label(), "(0 files)");
+    }
+
+    #[test]
+    fn test_build_ir_only_dirs() {
+        let root = create_test_node();
+        let emoji_mapper = EmojiMapper::new(false);
+        let mut stats = Stats::new();
+        let loc_counter = LocCounter::new(LocMode::Off);
+        let options = IrOptions {
+            only_dirs: true,
+            ..Default::default()
+        };
+
+        let mut ctx = AggregationContext {
+            emoji_mapper: &emoji_mapper,
+            stats: &mut stats,
+            loc_counter: &loc_counter,
+            options: &options,
+        };
+
+        let ir = build_ir(&root, &mut ctx);
+
+        assert!(ir.files.is_empty());
+        assert_eq!(ir.dirs.len(), 1);
+        assert!(ir.dirs[0].files.is_empty());
+    }
+
+    #[test]
+    fn test_build_ir_marks_generated() {
+        let root = Node {
+            name: "root".to_string(),
+            path: PathBuf::from("root"),
+            is_dir: true,
+            display_path: PathBuf::from("."),
+            children: vec![
+                Node {
+                    name: "yarn.lock".to_string(),
+                    path: PathBuf::from("root/yarn.lock"),
+                    is_dir: false,
+                    display_path: PathBuf::from("yarn.lock"),
+                    children: vec![],
+                    ..Default::default()
+                },
+                Node {
+                    name: "main.rs".to_string(),
+                    path: PathBuf::from("root/main.rs"),
+                    is_dir: false,
+                    display_path: PathBuf::from("main.rs"),
+                    children: vec![],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let emoji_mapper = EmojiMapper::new(false);
+        let mut stats = Stats::new();
+        let loc_counter = LocCounter::new(LocMode::Off);
+        let options = IrOptions {
+            generated: build_globset(&["*.lock".to_string()], true),
+            ..Default::default()
+        };
+
+        let mut ctx = AggregationContext {
+            emoji_mapper: &emoji_mapper,
+            stats: &mut stats,
+            loc_counter: &loc_counter,
+            options: &options,
+        };
+
+        let ir = build_ir(&root, &mut ctx);
+        let lock = ir.files.iter().find(|f| f.name == "yarn.lock").unwrap();
+        let main = ir.files.iter().find(|f| f.name == "main.rs").unwrap();
+        assert!(lock.generated);
+        assert!(!main.generated);
+    }
+
+    #[test]
+    fn test_build_globset_folds_case_like_filters() {
+        let patterns = ["*.LOCK".to_string()];
+        let folded = build_globset(&patterns, false).unwrap();
+        assert!(folded.is_match("yarn.lock"));
+        let exact = build_globset(&patterns, true).unwrap();
+        assert!(!exact.is_match("yarn.lock"));
+        assert!(exact.is_match("yarn.LOCK"));
     }
 
     #[test]
@@ -198,6 +762,13 @@ mod tests {
                     emoji: String::new(),
                     loc: None,
                     size_bytes: 0,
+                    loc_skipped: false,
+                    generated: false,
+                    highlighted: false,
+                    ignored: false,
+                    symlink_target: None,
+                    executable: false,
+                    annotation: None,
                 },
                 IrFile {
                     name: "file2.txt".to_string(),
@@ -207,6 +778,13 @@ mod tests {
                     emoji: String::new(),
                     loc: None,
                     size_bytes: 0,
+                    loc_skipped: false,
+                    generated: false,
+                    highlighted: false,
+                    ignored: false,
+                    symlink_target: None,
+                    executable: false,
+                    annotation: None,
                 },
             ],
             dirs: vec![IrDir {
@@ -214,7 +792,9 @@ mod tests {
                 display_path: PathBuf::from("test/subdir"),
                 files: vec![],
                 dirs: vec![],
+                ..Default::default()
             }],
+            ..Default::default()
         };
 
         assert_eq!(ir_dir.immediate_child_count(), (2, 1));
@@ -225,6 +805,7 @@ mod tests {
             display_path: PathBuf::from("empty"),
             files: vec![],
             dirs: vec![],
+            ..Default::default()
         };
 
         assert!(empty_dir.is_empty());
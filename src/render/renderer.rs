@@ -10,6 +10,18 @@ pub enum OutputFormat {
     Pipe,
     /// Terminal with Unicode tree branches
     Terminal,
+    /// Flat list of file paths
+    Flat,
+    /// Markdown table of files
+    Table,
+    /// Nested XML document
+    Xml,
+    /// gron-style assignments, one per line
+    Gron,
+    /// reStructuredText nested bullet list
+    Rst,
+    /// Nested TOML tables
+    Toml,
 }
 
 /// Configuration for rendering
@@ -37,8 +49,11 @@ pub trait Renderer {
     /// Render the tree structure
     fn render_tree(&mut self, root: &Node) -> String;
 
-    /// Render statistics footer
-    fn render_stats(&self, stats: &Stats) -> String;
+    /// Render statistics footer; none by default, for formats whose
+    /// document is the tree alone
+    fn render_stats(&self, _stats: &Stats) -> String {
+        String::new()
+    }
 
     /// Check if this renderer supports animations
     #[allow(dead_code)]
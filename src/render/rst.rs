@@ -0,0 +1,119 @@
+use crate::cli::Args;
+use crate::fs_tree::Node;
+use crate::render::pipeline::{IrBuilder, IrDir, IrEntry};
+use crate::render::renderer::{OutputFormat, Renderer};
+use std::path::Path;
+
+/// reStructuredText renderer for `--output rst`.
+/// Nested bullet lists of `` `name <path>`__ `` links. RST only nests a list
+/// when it is indented to the parent item's text and set off by blank lines.
+pub struct RstRenderer {
+    ir_builder: IrBuilder,
+}
+
+impl RstRenderer {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            ir_builder: IrBuilder::new(args),
+        }
+    }
+
+    fn render_dir(&self, dir: &IrDir, indent: &str, lines: &mut Vec<String>) {
+        for entry in dir.entries(self.ir_builder.options().dirs_last) {
+            match entry {
+                IrEntry::Dir(subdir) => {
+                    let target = format!("{}/", link_path(&subdir.display_path));
+                    let mut item = format!(
+                        "{}- {}",
+                        indent,
+                        link(&format!("{}/", subdir.name), &target)
+                    );
+                    if let Some(count) = subdir.collapsed_files {
+                        item.push_str(&format!(" ({} files, collapsed)", count));
+                    }
+                    lines.push(item);
+                    if !subdir.files.is_empty() || !subdir.dirs.is_empty() || subdir.omitted > 0 {
+                        lines.push(String::new());
+                        self.render_dir(subdir, &format!("{}  ", indent), lines);
+                        lines.push(String::new());
+                    }
+                }
+                IrEntry::File(file) => {
+                    let target = link_path(&file.display_path);
+                    lines.push(format!("{}- {}", indent, link(&file.name, &target)));
+                }
+            }
+        }
+        if dir.omitted > 0 {
+            lines.push(format!("{}- … {} more", indent, dir.omitted));
+        }
+    }
+}
+
+/// Forward-slash form of a display path for use as a link target
+fn link_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Anonymous hyperlink (`__`), so files sharing a name (`mod.rs`) don't
+/// collide as duplicate explicit targets. RST strips whitespace from link
+/// targets unless it is backslash-escaped.
+fn link(text: &str, target: &str) -> String {
+    format!(
+        "`{} <{}>`__",
+        escape(text),
+        escape(target).replace(' ', "\\ ")
+    )
+}
+
+/// Backslash-escape characters that would end the link text or target early
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+impl Renderer for RstRenderer {
+    fn render_tree(&mut self, root: &Node) -> String {
+        let ir = self.ir_builder.build(root);
+
+        let mut lines = Vec::new();
+        self.render_dir(&ir, "", &mut lines);
+
+        // Nested lists that end together leave runs of blank lines
+        lines.dedup_by(|a, b| a.is_empty() && b.is_empty());
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+
+        let mut output = lines.join("\n");
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Rst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link() {
+        assert_eq!(link("main.rs", "src/main.rs"), "`main.rs <src/main.rs>`__");
+        assert_eq!(link("a`b<c>", "a`b<c>"), "`a\\`b\\<c\\> <a\\`b\\<c\\>>`__");
+        assert_eq!(
+            link("my notes.md", "my docs/my notes.md"),
+            "`my notes.md <my\\ docs/my\\ notes.md>`__"
+        );
+    }
+}
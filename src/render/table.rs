@@ -0,0 +1,96 @@
+use crate::cli::Args;
+use crate::fs_tree::Node;
+use crate::render::pipeline::{collect_files, IrBuilder, IrDir};
+use crate::render::renderer::{OutputFormat, Renderer};
+use crate::util::format::format_size;
+
+/// Markdown table renderer for `--output md-table`.
+/// One row per file, sorted by path: `Path | Type | LOC | Size`. Directories
+/// with unlisted files get a `dir/` row noting how many.
+pub struct TableRenderer {
+    ir_builder: IrBuilder,
+}
+
+impl TableRenderer {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            ir_builder: IrBuilder::new(args),
+        }
+    }
+}
+
+/// Rows for directories whose files are not listed: `… N more` where
+/// `--max-children-per-dir` cut entries, `N files, collapsed` for a collapsed one
+fn collect_dir_notes(dir: &IrDir, rows: &mut Vec<(String, String, String, String)>) {
+    let path = if dir.display_path.as_os_str().is_empty() {
+        "./".to_string()
+    } else {
+        format!("{}/", dir.display_path.to_string_lossy().replace('\\', "/"))
+    };
+    let note = |text: String| (path.clone(), text, "-".to_string(), "-".to_string());
+
+    if let Some(count) = dir.collapsed_files {
+        rows.push(note(format!("{} files, collapsed", count)));
+    }
+    if dir.omitted > 0 {
+        rows.push(note(format!("… {} more", dir.omitted)));
+    }
+    for subdir in &dir.dirs {
+        collect_dir_notes(subdir, rows);
+    }
+}
+
+/// Escape characters that would break a table cell
+fn escape_cell(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+impl Renderer for TableRenderer {
+    fn render_tree(&mut self, root: &Node) -> String {
+        let ir = self.ir_builder.build(root);
+
+        let mut rows: Vec<(String, String, String, String)> = collect_files(&ir)
+            .into_iter()
+            .map(|file| {
+                (
+                    file.display_path.to_string_lossy().replace('\\', "/"),
+                    file.file_type.display_name().to_string(),
+                    file.loc
+                        .map(|l| l.to_string())
+                        .unwrap_or_else(|| "-".into()),
+                    format_size(file.size_bytes),
+                )
+            })
+            .collect();
+        collect_dir_notes(&ir, &mut rows);
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut output =
+            String::from("| Path | Type | LOC | Size |\n|------|------|----:|-----:|\n");
+        for (path, file_type, loc, size) in rows {
+            output.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                escape_cell(&path),
+                file_type,
+                loc,
+                size
+            ));
+        }
+        output
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_cell() {
+        assert_eq!(escape_cell("a|b.txt"), "a\\|b.txt");
+        assert_eq!(escape_cell("plain.rs"), "plain.rs");
+    }
+}
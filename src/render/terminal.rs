@@ -1,13 +1,18 @@
-use crate::cli::Args;
+use crate::cli::{Args, ColorBy};
 use crate::fs_tree::{LocCounter, Node};
 use crate::output::stats::Stats;
+use crate::profile::emoji::EMOJI_MAP_ENV;
 use crate::profile::{EmojiMapper, FileType};
-use crate::render::pipeline::{build_ir, AggregationContext, IrDir, IrFile};
+use crate::render::pipeline::{build_ir, AggregationContext, IrDir, IrEntry, IrFile, IrOptions};
 use crate::render::renderer::{OutputFormat, Renderer};
 use crate::terminal::capabilities::TerminalCapabilities;
 use crate::terminal::detect::TerminalDetector;
-use crate::util::format::{format_loc_display, is_global_outlier, loc_category, loc_to_bar};
+use crate::util::format::{
+    format_loc_display, is_global_outlier, loc_category, loc_to_bar, size_mark, truncate_name,
+    SizeBadge,
+};
 use std::path::Path;
+use unicode_width::UnicodeWidthStr;
 
 /// Terminal renderer with Unicode tree branches
 pub struct TerminalRenderer<'a> {
@@ -16,14 +21,26 @@ pub struct TerminalRenderer<'a> {
     emoji_mapper: EmojiMapper,
     stats: Stats,
     loc_counter: LocCounter,
+    ir_options: IrOptions,
     output: String,
     global_threshold: usize, // Threshold for global outliers (95th percentile)
+    depth_colors: bool,      // Tint branches per nesting level (--depth-colors)
+    color_by: Option<ColorBy>, // File name coloring (--color-by)
 }
 
+/// Columns taken by the LOC columns after the name: `  [bar]  {loc:>6} (XXL) ★`
+const LOC_SUFFIX_WIDTH: usize = 30;
+
+/// ANSI colors cycled through by nesting depth
+const DEPTH_PALETTE: &[&str] = &["36", "32", "33", "35", "34"];
+
 impl<'a> TerminalRenderer<'a> {
     pub fn new(args: &'a Args) -> Self {
-        let detector = TerminalDetector::new();
-        let capabilities = TerminalCapabilities::new();
+        let detector = TerminalDetector::for_output(args.output);
+        let capabilities =
+            TerminalCapabilities::with_detector(TerminalDetector::for_output(args.output))
+                .with_width(args.term_width)
+                .with_colors(!args.deterministic);
 
         let use_emoji = args.is_fun_enabled(detector.is_tty());
         let mut emoji_mapper = EmojiMapper::new(use_emoji);
@@ -36,6 +53,13 @@ impl<'a> TerminalRenderer<'a> {
                     emoji_map_path, e
                 );
             }
+        } else if let Ok(value) = std::env::var(EMOJI_MAP_ENV) {
+            if let Err(e) = emoji_mapper.load_from_env_value(&value) {
+                eprintln!(
+                    "Warning: Failed to load emoji map from {}: {}",
+                    EMOJI_MAP_ENV, e
+                );
+            }
         }
 
         // Apply CLI emoji overrides
@@ -43,91 +67,189 @@ impl<'a> TerminalRenderer<'a> {
             emoji_mapper.parse_cli_emoji(emoji_arg);
         }
 
+        if let Some(glyph) = &args.emoji_for_unknown {
+            emoji_mapper.set_unknown_emoji(glyph.clone());
+        }
+
+        let depth_colors = args.depth_colors && capabilities.supports_colors();
+        let color_by = args.color_by.filter(|_| capabilities.supports_colors());
+
         Self {
             args,
             capabilities,
             emoji_mapper,
-            stats: Stats::new(),
+            stats: Stats::from_args(args),
             loc_counter: LocCounter::new(args.loc.clone()),
+            ir_options: IrOptions::from_args(args),
             output: String::new(),
             global_threshold: 0,
+            depth_colors,
+            color_by,
         }
     }
 
-    #[allow(clippy::only_used_in_recursion)]
+    /// Display width and LOC of every file row below `dir`, whose children
+    /// sit at `depth` behind a prefix `prefix_width` columns wide
     fn collect_all_files(
         &self,
         dir: &IrDir,
-        files: &mut Vec<(String, Option<usize>)>,
-        prefix_len: usize,
+        depth: usize,
+        prefix_width: usize,
+        files: &mut Vec<(usize, Option<usize>)>,
     ) {
+        let tree_chars = self.capabilities.tree_chars();
         for subdir in &dir.dirs {
-            let new_prefix_len = prefix_len + 2;
-            self.collect_all_files(subdir, files, new_prefix_len);
+            let child_prefix_width = prefix_width + tree_chars.vertical.width();
+            self.collect_all_files(subdir, depth + 1, child_prefix_width, files);
         }
 
         for file in &dir.files {
-            files.push((file.name.clone(), file.loc));
+            let row_width =
+                prefix_width + tree_chars.branch.width() + self.file_label(file, depth).width();
+            files.push((row_width, file.loc));
         }
     }
 
-    fn render_ir_dir_aligned(&mut self, dir: &IrDir, prefix: &str, max_name_width: usize) {
-        let tree_chars = self.capabilities.tree_chars();
+    /// Depth label, emoji and name: everything on a file row after the branch
+    fn file_label(&self, file: &IrFile, depth: usize) -> String {
+        let emoji_str = if !file.emoji.is_empty() {
+            format!("{} ", file.emoji)
+        } else {
+            String::new()
+        };
+        format!(
+            "{}{}{}",
+            self.args.depth_label(depth),
+            emoji_str,
+            file.display_name(self.args)
+        )
+    }
 
+    /// Render the children of `dir`, which sit at nesting `depth`
+    fn render_ir_dir_aligned(
+        &mut self,
+        dir: &IrDir,
+        prefix: &str,
+        depth: usize,
+        max_name_width: usize,
+    ) {
         let max_loc_in_dir = dir.files.iter().filter_map(|f| f.loc).max().unwrap_or(0);
 
-        for (i, subdir) in dir.dirs.iter().enumerate() {
-            let subdir_is_last = i == dir.dirs.len() - 1 && dir.files.is_empty();
+        let entries = dir.entries(self.ir_options.dirs_last);
+        let total = entries.len();
 
-            let dir_emoji = self
-                .emoji_mapper
-                .get_emoji(&subdir.display_path, FileType::Directory);
-            let emoji_str = if !dir_emoji.is_empty() {
-                format!("{} ", dir_emoji)
-            } else {
-                String::new()
-            };
+        for (i, entry) in entries.into_iter().enumerate() {
+            let is_last = i + 1 == total && dir.omitted == 0;
+            match entry {
+                IrEntry::Dir(subdir) => {
+                    self.render_ir_subdir(subdir, prefix, depth, is_last, max_name_width)
+                }
+                IrEntry::File(file) => self.render_ir_file_with_local_scale(
+                    file,
+                    prefix,
+                    depth,
+                    is_last,
+                    max_name_width,
+                    max_loc_in_dir,
+                ),
+            }
+        }
 
-            self.output.push_str(&format!(
-                "{}{}{}{}/\n",
-                prefix,
-                if subdir_is_last {
-                    tree_chars.last_branch
-                } else {
-                    tree_chars.branch
-                },
-                emoji_str,
-                subdir.name
-            ));
+        if dir.omitted > 0 {
+            let branch = self.paint_branch(depth, self.capabilities.tree_chars().last_branch);
+            self.output
+                .push_str(&format!("{}{}… {} more\n", prefix, branch, dir.omitted));
+        }
+    }
 
-            let new_prefix = format!(
-                "{}{}",
-                prefix,
-                if subdir_is_last {
-                    tree_chars.empty
-                } else {
-                    tree_chars.vertical
-                }
-            );
-            self.render_ir_dir_aligned(subdir, &new_prefix, max_name_width);
+    /// Branch connector, tinted by nesting depth when `--depth-colors` is active
+    fn paint_branch(&self, depth: usize, branch: &str) -> String {
+        if !self.depth_colors {
+            return branch.to_string();
+        }
+        let color = DEPTH_PALETTE[depth % DEPTH_PALETTE.len()];
+        format!("\x1b[{}m{}\x1b[0m", color, branch)
+    }
+
+    /// ANSI color for a file name under `--color-by`
+    fn name_color(&self, file: &IrFile, depth: usize) -> Option<&'static str> {
+        match self.color_by? {
+            ColorBy::Type => file.file_type.ansi_color(),
+            ColorBy::Depth => Some(DEPTH_PALETTE[depth % DEPTH_PALETTE.len()]),
+            ColorBy::Size => Some(match size_mark(file.size_bytes).badge {
+                SizeBadge::Xs | SizeBadge::S => "32",
+                SizeBadge::M | SizeBadge::L => "33",
+                SizeBadge::Xl | SizeBadge::Xxl => "31",
+            }),
         }
+    }
+
+    fn render_ir_subdir(
+        &mut self,
+        subdir: &IrDir,
+        prefix: &str,
+        depth: usize,
+        subdir_is_last: bool,
+        max_name_width: usize,
+    ) {
+        let tree_chars = self.capabilities.tree_chars();
+
+        let dir_emoji = self
+            .emoji_mapper
+            .get_emoji(&subdir.display_path, FileType::Directory);
+        let emoji_str = if !dir_emoji.is_empty() {
+            format!("{} ", dir_emoji)
+        } else {
+            String::new()
+        };
+
+        let branch = if subdir_is_last {
+            tree_chars.last_branch
+        } else {
+            tree_chars.branch
+        };
 
-        for (i, file) in dir.files.iter().enumerate() {
-            let file_is_last = i == dir.files.len() - 1;
-            self.render_ir_file_with_local_scale(
-                file,
-                prefix,
-                file_is_last,
-                max_name_width,
-                max_loc_in_dir,
-            );
+        self.output.push_str(&format!(
+            "{}{}{}{}{}{}{}{}{}\n",
+            prefix,
+            self.paint_branch(depth, branch),
+            self.args.depth_label(depth),
+            emoji_str,
+            truncate_name(&subdir.name, self.args.max_name_length),
+            self.args.dir_suffix(),
+            if subdir.ignored { "  (ignored)" } else { "" },
+            subdir
+                .collapsed_files
+                .map(|count| format!("  ({} files, collapsed)", count))
+                .unwrap_or_default(),
+            subdir
+                .annotation
+                .as_ref()
+                .map(|a| format!("  — {}", a))
+                .unwrap_or_default()
+        ));
+
+        let new_prefix = format!(
+            "{}{}",
+            prefix,
+            if subdir_is_last {
+                tree_chars.empty
+            } else {
+                tree_chars.vertical
+            }
+        );
+        if self.args.shows_dir_subtotal(subdir.totals().0) {
+            self.output
+                .push_str(&format!("{}{}\n", new_prefix, subdir.totals_label()));
         }
+        self.render_ir_dir_aligned(subdir, &new_prefix, depth + 1, max_name_width);
     }
 
     fn render_ir_file_with_local_scale(
         &mut self,
         file: &IrFile,
         prefix: &str,
+        depth: usize,
         is_last: bool,
         max_name_width: usize,
         max_loc_in_dir: usize,
@@ -140,19 +262,28 @@ impl<'a> TerminalRenderer<'a> {
             tree_chars.branch
         };
 
-        let emoji_str = if !file.emoji.is_empty() {
-            format!("{} ", file.emoji)
-        } else {
-            String::new()
-        };
+        // Measured in display columns, the same way collect_all_files does
+        let row_width = prefix.width() + branch.width();
 
         self.output.push_str(prefix);
-        self.output.push_str(branch);
-        let name_with_emoji = format!("{}{}", emoji_str, file.name);
-        self.output.push_str(&name_with_emoji);
+        let branch = self.paint_branch(depth, branch);
+        self.output.push_str(&branch);
+        let name_with_emoji = self.file_label(file, depth);
+        let dimmed = file.generated && self.capabilities.supports_colors();
+        if dimmed {
+            self.output
+                .push_str(&format!("\x1b[2m{}\x1b[0m", name_with_emoji));
+        } else if let Some(color) = self.name_color(file, depth) {
+            self.output
+                .push_str(&format!("\x1b[{}m{}\x1b[0m", color, name_with_emoji));
+        } else {
+            self.output.push_str(&name_with_emoji);
+        }
 
         if let Some(loc) = file.loc {
-            let current_len = prefix.len() + 2 + name_with_emoji.len();
+            // Display columns, not bytes: box-drawing prefixes and emoji are
+            // multi-byte, and most emoji take two columns
+            let current_len = row_width + name_with_emoji.width();
             let padding = if current_len < max_name_width {
                 " ".repeat(max_name_width - current_len)
             } else {
@@ -175,6 +306,26 @@ impl<'a> TerminalRenderer<'a> {
             ));
         }
 
+        if file.loc_skipped {
+            self.output.push_str("  (too large to count)");
+        }
+
+        if file.highlighted {
+            self.output.push_str("  ⭐");
+        }
+
+        if file.generated && !dimmed {
+            self.output.push_str("  (generated)");
+        }
+
+        if file.ignored {
+            self.output.push_str("  (ignored)");
+        }
+
+        if let Some(annotation) = &file.annotation {
+            self.output.push_str(&format!("  — {}", annotation));
+        }
+
         self.output.push('\n');
     }
 }
@@ -192,19 +343,22 @@ impl<'a> Renderer for TerminalRenderer<'a> {
             emoji_mapper: &self.emoji_mapper,
             stats: &mut self.stats,
             loc_counter: &self.loc_counter,
+            options: &self.ir_options,
         };
 
         let ir = build_ir(root, &mut ctx);
 
         let mut all_files = Vec::new();
-        self.collect_all_files(&ir, &mut all_files, 0);
-
-        let max_name_width = all_files
-            .iter()
-            .map(|(name, _)| name.len())
-            .max()
-            .unwrap_or(0)
-            + 10;
+        self.collect_all_files(&ir, 0, 0, &mut all_files);
+
+        // Bars start two columns past the widest row, wherever it sits in the tree
+        let max_name_width = all_files.iter().map(|(width, _)| *width).max().unwrap_or(0) + 2;
+        // Keep the LOC columns inside a terminal of known width; longer names
+        // push past it
+        let max_name_width = match self.capabilities.known_width() {
+            Some(width) => max_name_width.min(width.saturating_sub(LOC_SUFFIX_WIDTH)),
+            None => max_name_width,
+        };
 
         let mut all_locs: Vec<usize> = all_files.iter().filter_map(|(_, loc)| *loc).collect();
         all_locs.sort_unstable_by(|a, b| b.cmp(a));
@@ -219,9 +373,9 @@ impl<'a> Renderer for TerminalRenderer<'a> {
             usize::MAX
         };
 
-        self.render_ir_dir_aligned(&ir, "", max_name_width);
+        self.render_ir_dir_aligned(&ir, "", 0, max_name_width);
 
-        if self.args.should_show_stats() {
+        if self.args.should_show_stats(self.stats.total_files()) {
             self.output.push('\n');
             self.output.push_str(&self.render_stats(&self.stats));
         }
@@ -252,28 +406,11 @@ impl<'a> Renderer for TerminalRenderer<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::{FunMode, LocMode, StatsMode};
+    use clap::Parser;
     use std::path::PathBuf;
 
     fn create_test_args() -> Args {
-        Args {
-            target: ".".to_string(),
-            level: None,
-            include: vec![],
-            exclude: vec![],
-            use_gitignore: crate::cli::UseGitignoreMode::Auto,
-            emoji: vec![],
-            emoji_map: None,
-            fun: FunMode::Off,
-            no_anim: false,
-            stats: StatsMode::Off,
-            loc: LocMode::Off,
-            contents: false,
-            max_chars: None,
-            contents_mode: crate::cli::ContentsMode::Head,
-            safe: true,
-            unsafe_mode: false,
-        }
+        Args::parse_from(["tree2md", "--fun", "off", "--stats", "off", "--loc", "off"])
     }
 
     #[test]
@@ -298,7 +435,9 @@ mod tests {
                         is_dir: false,
                         display_path: PathBuf::from("dir1/file1.txt"),
                         children: vec![],
+                        ..Default::default()
                     }],
+                    ..Default::default()
                 },
                 Node {
                     name: "file2.rs".to_string(),
@@ -306,8 +445,10 @@ mod tests {
                     is_dir: false,
                     display_path: PathBuf::from("file2.rs"),
                     children: vec![],
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let output = renderer.render_tree(&root);
@@ -316,6 +457,85 @@ mod tests {
         assert!(output.contains("file2.rs"));
     }
 
+    #[test]
+    fn test_terminal_depth_colors() {
+        let args = create_test_args();
+        let mut renderer = TerminalRenderer::new(&args);
+        // Force color mode regardless of the test environment
+        renderer.depth_colors = true;
+
+        let root = Node {
+            name: "test".to_string(),
+            path: PathBuf::from("test"),
+            is_dir: true,
+            display_path: PathBuf::from("."),
+            children: vec![Node {
+                name: "a".to_string(),
+                path: PathBuf::from("test/a"),
+                is_dir: true,
+                display_path: PathBuf::from("a"),
+                children: vec![Node {
+                    name: "b".to_string(),
+                    path: PathBuf::from("test/a/b"),
+                    is_dir: true,
+                    display_path: PathBuf::from("a/b"),
+                    children: vec![],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let output = renderer.render_tree(&root);
+        let line_a = output.lines().find(|l| l.contains("a/")).unwrap();
+        let line_b = output.lines().find(|l| l.contains("b/")).unwrap();
+        assert!(line_a.contains("\x1b[36m"), "depth 0: {:?}", line_a);
+        assert!(line_b.contains("\x1b[32m"), "depth 1: {:?}", line_b);
+
+        // Without the flag there are no escape codes
+        let mut plain = TerminalRenderer::new(&args);
+        assert!(!plain.render_tree(&root).contains('\x1b'));
+    }
+
+    #[test]
+    fn test_terminal_color_by_size() {
+        let args = create_test_args();
+        let mut renderer = TerminalRenderer::new(&args);
+        // Force color mode regardless of the test environment
+        renderer.color_by = Some(ColorBy::Size);
+
+        let root = Node {
+            name: "test".to_string(),
+            path: PathBuf::from("test"),
+            is_dir: true,
+            display_path: PathBuf::from("."),
+            children: vec![
+                Node {
+                    name: "big.bin".to_string(),
+                    path: PathBuf::from("test/big.bin"),
+                    display_path: PathBuf::from("big.bin"),
+                    size: 8 * 1024 * 1024,
+                    ..Default::default()
+                },
+                Node {
+                    name: "small.txt".to_string(),
+                    path: PathBuf::from("test/small.txt"),
+                    display_path: PathBuf::from("small.txt"),
+                    size: 100,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let output = renderer.render_tree(&root);
+        let big = output.lines().find(|l| l.contains("big.bin")).unwrap();
+        let small = output.lines().find(|l| l.contains("small.txt")).unwrap();
+        assert!(big.contains("\x1b[31m"), "large file: {:?}", big);
+        assert!(small.contains("\x1b[32m"), "small file: {:?}", small);
+    }
+
     #[test]
     fn test_terminal_renderer_output_format() {
         let args = create_test_args();
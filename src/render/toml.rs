@@ -0,0 +1,75 @@
+use crate::cli::Args;
+use crate::fs_tree::Node;
+use crate::render::pipeline::{IrBuilder, IrDir};
+use crate::render::renderer::{OutputFormat, Renderer};
+use toml::{Table, Value};
+
+/// TOML renderer for `--output toml`.
+/// Each directory is a table with a `files` array of `{ name, size, loc }`
+/// tables and its subdirectories under `dirs`, keyed by name (`[dirs.src]`),
+/// so a directory named `files` cannot clash with the array. `omitted` and
+/// `collapsed` carry the `--max-children-per-dir` and collapsed-directory counts.
+pub struct TomlRenderer {
+    ir_builder: IrBuilder,
+}
+
+impl TomlRenderer {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            ir_builder: IrBuilder::new(args),
+        }
+    }
+}
+
+/// Table for one directory; the serializer quotes keys that need it
+fn dir_table(dir: &IrDir) -> Table {
+    let mut table = Table::new();
+    if dir.omitted > 0 {
+        table.insert("omitted".into(), Value::Integer(dir.omitted as i64));
+    }
+    if let Some(count) = dir.collapsed_files {
+        table.insert("collapsed".into(), Value::Integer(count as i64));
+    }
+
+    if !dir.files.is_empty() {
+        let files = dir
+            .files
+            .iter()
+            .map(|file| {
+                let mut entry = Table::new();
+                entry.insert("name".into(), Value::String(file.name.clone()));
+                entry.insert("size".into(), Value::Integer(file.size_bytes as i64));
+                if let Some(loc) = file.loc {
+                    entry.insert("loc".into(), Value::Integer(loc as i64));
+                }
+                Value::Table(entry)
+            })
+            .collect();
+        table.insert("files".into(), Value::Array(files));
+    }
+
+    if !dir.dirs.is_empty() {
+        let dirs = dir
+            .dirs
+            .iter()
+            .map(|subdir| (subdir.name.clone(), Value::Table(dir_table(subdir))))
+            .collect();
+        table.insert("dirs".into(), Value::Table(dirs));
+    }
+    table
+}
+
+impl Renderer for TomlRenderer {
+    fn render_tree(&mut self, root: &Node) -> String {
+        let ir = self.ir_builder.build(root);
+
+        let mut document = Table::new();
+        document.insert("name".into(), Value::String(ir.name.clone()));
+        document.extend(dir_table(&ir));
+        toml::to_string(&document).unwrap_or_default()
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Toml
+    }
+}
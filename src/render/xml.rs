@@ -0,0 +1,99 @@
+use crate::cli::Args;
+use crate::fs_tree::Node;
+use crate::render::pipeline::{IrBuilder, IrDir, IrEntry};
+use crate::render::renderer::{OutputFormat, Renderer};
+
+/// XML renderer for `--output xml`.
+/// Nested `<directory name="...">` elements with `<file name="..." size="..." loc="..."/>` leaves.
+/// `omitted="N"` counts entries cut by `--max-children-per-dir`; `collapsed="N"`
+/// is the file count of a collapsed directory.
+pub struct XmlRenderer {
+    ir_builder: IrBuilder,
+}
+
+impl XmlRenderer {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            ir_builder: IrBuilder::new(args),
+        }
+    }
+
+    fn render_dir(&self, dir: &IrDir, depth: usize, out: &mut String) {
+        let indent = "  ".repeat(depth);
+        out.push_str(&format!(
+            "{}<directory name=\"{}\"",
+            indent,
+            escape_attr(&dir.name)
+        ));
+        if dir.omitted > 0 {
+            out.push_str(&format!(" omitted=\"{}\"", dir.omitted));
+        }
+        if let Some(count) = dir.collapsed_files {
+            out.push_str(&format!(" collapsed=\"{}\"", count));
+        }
+        out.push_str(">\n");
+
+        for entry in dir.entries(self.ir_builder.options().dirs_last) {
+            match entry {
+                IrEntry::Dir(subdir) => self.render_dir(subdir, depth + 1, out),
+                IrEntry::File(file) => {
+                    out.push_str(&format!(
+                        "{}  <file name=\"{}\" size=\"{}\"",
+                        indent,
+                        escape_attr(&file.name),
+                        file.size_bytes
+                    ));
+                    if let Some(loc) = file.loc {
+                        out.push_str(&format!(" loc=\"{}\"", loc));
+                    }
+                    out.push_str("/>\n");
+                }
+            }
+        }
+
+        out.push_str(&format!("{}</directory>\n", indent));
+    }
+}
+
+/// Escape text for use inside a double-quoted XML attribute
+fn escape_attr(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl Renderer for XmlRenderer {
+    fn render_tree(&mut self, root: &Node) -> String {
+        let ir = self.ir_builder.build(root);
+
+        let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.render_dir(&ir, 0, &mut output);
+        output
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Xml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_attr() {
+        assert_eq!(
+            escape_attr("a&b <c> \"d\""),
+            "a&amp;b &lt;c&gt; &quot;d&quot;"
+        );
+        assert_eq!(escape_attr("plain.rs"), "plain.rs");
+    }
+}
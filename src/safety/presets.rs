@@ -106,12 +106,18 @@ impl SafetyPreset {
 
     /// Check if a path matches any safety pattern
     pub fn matches(&self, path: &str) -> bool {
+        self.matching_rule(path).is_some()
+    }
+
+    /// The first safety pattern matching a path, if any
+    pub fn matching_rule(&self, path: &str) -> Option<&str> {
         // Normalize path separators to forward slashes for consistent matching
         let normalized_path = path.replace('\\', "/");
 
         self.patterns
             .iter()
-            .any(|pattern| pattern.matches(&normalized_path))
+            .find(|pattern| pattern.matches(&normalized_path))
+            .map(|pattern| pattern.as_str())
     }
 }
 
@@ -3,8 +3,8 @@ use crate::terminal::detect::{TerminalDetector, TerminalMode};
 /// Terminal capabilities and features
 pub struct TerminalCapabilities {
     detector: TerminalDetector,
-    #[allow(dead_code)]
     width: Option<usize>,
+    colors: bool,
 }
 
 impl TerminalCapabilities {
@@ -12,13 +12,34 @@ impl TerminalCapabilities {
         let detector = TerminalDetector::new();
         let width = Self::detect_width();
 
-        Self { detector, width }
+        Self {
+            detector,
+            width,
+            colors: true,
+        }
     }
 
-    #[allow(dead_code)]
     pub fn with_detector(detector: TerminalDetector) -> Self {
         let width = Self::detect_width();
-        Self { detector, width }
+        Self {
+            detector,
+            width,
+            colors: true,
+        }
+    }
+
+    /// Force the width instead of the detected one (`--term-width`)
+    pub fn with_width(mut self, width: Option<usize>) -> Self {
+        if width.is_some() {
+            self.width = width;
+        }
+        self
+    }
+
+    /// Turn colors off regardless of detection (`--deterministic`)
+    pub fn with_colors(mut self, enabled: bool) -> Self {
+        self.colors &= enabled;
+        self
     }
 
     /// Get terminal width
@@ -27,6 +48,11 @@ impl TerminalCapabilities {
         self.width.unwrap_or(80)
     }
 
+    /// Terminal width when it was given or detected, rather than assumed
+    pub fn known_width(&self) -> Option<usize> {
+        self.width
+    }
+
     /// Check if we should display animations
     #[allow(dead_code)]
     pub fn supports_animation(&self) -> bool {
@@ -52,7 +78,7 @@ impl TerminalCapabilities {
     /// Check if we should use colors
     #[allow(dead_code)]
     pub fn supports_colors(&self) -> bool {
-        self.detector.should_use_colors()
+        self.colors && self.detector.should_use_colors()
     }
 
     /// Check if we should use Unicode tree characters
@@ -79,16 +105,25 @@ impl TerminalCapabilities {
         }
     }
 
-    /// Detect terminal width
+    /// Detect terminal width: `$COLUMNS`, then the size of the terminal on
+    /// stdout. `None` when neither is available (e.g. output is piped).
     fn detect_width() -> Option<usize> {
-        // Try to get terminal size using terminal_size crate would be better,
-        // but for now we'll use a simple approach
         if let Ok(cols) = std::env::var("COLUMNS") {
-            cols.parse().ok()
-        } else {
-            // Default terminal width
-            Some(80)
+            return cols.parse().ok();
         }
+        Self::stdout_width()
+    }
+
+    #[cfg(unix)]
+    fn stdout_width() -> Option<usize> {
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+        (ok && size.ws_col > 0).then_some(size.ws_col as usize)
+    }
+
+    #[cfg(not(unix))]
+    fn stdout_width() -> Option<usize> {
+        None
     }
 
     /// Get the output mode
@@ -1,3 +1,4 @@
+use crate::cli::OutputMode;
 use std::env;
 
 /// Detects terminal environment and capabilities
@@ -10,7 +11,6 @@ pub struct TerminalDetector {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TerminalMode {
     Tty,
-    #[allow(dead_code)]
     Plain,
 }
 
@@ -28,12 +28,26 @@ impl TerminalDetector {
     }
 
     /// Force a specific terminal mode (for testing or user override)
-    #[allow(dead_code)]
     pub fn with_mode(mut self, mode: TerminalMode) -> Self {
         self.force_mode = Some(mode);
         self
     }
 
+    /// Detector honoring an explicit `--output` choice
+    pub fn for_output(output: OutputMode) -> Self {
+        let detector = Self::new();
+        match output {
+            OutputMode::Auto => detector,
+            OutputMode::Tty => detector.with_mode(TerminalMode::Tty),
+            OutputMode::Pipe
+            | OutputMode::MdTable
+            | OutputMode::Xml
+            | OutputMode::Gron
+            | OutputMode::Rst
+            | OutputMode::Toml => detector.with_mode(TerminalMode::Plain),
+        }
+    }
+
     /// Check if we're in a TTY environment suitable for rich output
     pub fn is_tty(&self) -> bool {
         if let Some(mode) = self.force_mode {
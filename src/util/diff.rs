@@ -0,0 +1,230 @@
+/// Lines of unchanged context shown around each change
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Line-based diff between two texts, rendered as unified hunks
+/// (`@@ -l,n +l,n @@` followed by `-`/`+`/` ` lines) with a few lines of
+/// context. Identical texts give an empty string.
+pub fn line_diff(old: &str, new: &str) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    let mut edits = Vec::with_capacity(a.len().max(b.len()));
+    diff_range(&a, &b, &mut edits);
+
+    let changes: Vec<usize> = (0..edits.len())
+        .filter(|&i| edits[i] != Edit::Equal)
+        .collect();
+
+    let mut out = String::new();
+    let mut group_start = 0;
+    while group_start < changes.len() {
+        // Changes closer than two contexts apart share a hunk
+        let mut group_end = group_start;
+        while group_end + 1 < changes.len()
+            && changes[group_end + 1] - changes[group_end] <= 2 * CONTEXT + 1
+        {
+            group_end += 1;
+        }
+        let start = changes[group_start].saturating_sub(CONTEXT);
+        let end = (changes[group_end] + 1 + CONTEXT).min(edits.len());
+        write_hunk(&a, &b, &edits, start, end, &mut out);
+        group_start = group_end + 1;
+    }
+    out
+}
+
+/// Append the hunk covering `edits[start..end]`
+fn write_hunk(a: &[&str], b: &[&str], edits: &[Edit], start: usize, end: usize, out: &mut String) {
+    // Line positions at `start`: every edit before it consumed old, new or both
+    let old_pos = edits[..start]
+        .iter()
+        .filter(|&&e| e != Edit::Insert)
+        .count();
+    let new_pos = edits[..start]
+        .iter()
+        .filter(|&&e| e != Edit::Delete)
+        .count();
+    let old_len = edits[start..end]
+        .iter()
+        .filter(|&&e| e != Edit::Insert)
+        .count();
+    let new_len = edits[start..end]
+        .iter()
+        .filter(|&&e| e != Edit::Delete)
+        .count();
+
+    // An empty side is numbered by the line it follows, as in `diff -u`
+    let first_line = |pos: usize, len: usize| if len == 0 { pos } else { pos + 1 };
+    out.push_str(&format!(
+        "@@ -{},{} +{},{} @@\n",
+        first_line(old_pos, old_len),
+        old_len,
+        first_line(new_pos, new_len),
+        new_len
+    ));
+
+    let (mut i, mut j) = (old_pos, new_pos);
+    for edit in &edits[start..end] {
+        match edit {
+            Edit::Equal => {
+                out.push_str(&format!(" {}\n", a[i]));
+                i += 1;
+                j += 1;
+            }
+            Edit::Delete => {
+                out.push_str(&format!("-{}\n", a[i]));
+                i += 1;
+            }
+            Edit::Insert => {
+                out.push_str(&format!("+{}\n", b[j]));
+                j += 1;
+            }
+        }
+    }
+}
+
+/// Myers' linear-space diff: split both sides at the middle snake of an
+/// optimal edit path and recurse, so memory stays O(n + m)
+fn diff_range(a: &[&str], b: &[&str], edits: &mut Vec<Edit>) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let (a, b) = (&a[prefix..], &b[prefix..]);
+    let suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[..a.len() - suffix], &b[..b.len() - suffix]);
+
+    edits.extend(std::iter::repeat_n(Edit::Equal, prefix));
+    if a.is_empty() {
+        edits.extend(std::iter::repeat_n(Edit::Insert, b.len()));
+    } else if b.is_empty() {
+        edits.extend(std::iter::repeat_n(Edit::Delete, a.len()));
+    } else {
+        let (x, y, u, v) = middle_snake(a, b);
+        diff_range(&a[..x], &b[..y], edits);
+        edits.extend(std::iter::repeat_n(Edit::Equal, u - x));
+        diff_range(&a[u..], &b[v..], edits);
+    }
+    edits.extend(std::iter::repeat_n(Edit::Equal, suffix));
+}
+
+/// The snake `(x, y) -> (u, v)` where forward and backward searches meet.
+/// Both sides must be non-empty and differ in their first and last lines.
+fn middle_snake(a: &[&str], b: &[&str]) -> (usize, usize, usize, usize) {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d + 1;
+    let mut forward = vec![0isize; (2 * max_d + 3) as usize];
+    let mut backward = vec![0isize; (2 * max_d + 3) as usize];
+    let at = |k: isize| (k + offset) as usize;
+
+    for d in 0..=max_d {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                forward[at(k + 1)]
+            } else {
+                forward[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[at(k)] = x;
+
+            // Backward diagonal `delta - k` is the same line of the grid
+            let c = delta - k;
+            if odd && (-(d - 1)..=d - 1).contains(&c) && x + backward[at(c)] >= n {
+                return (x0 as usize, y0 as usize, x as usize, y as usize);
+            }
+        }
+
+        // The backward search runs over both sides reversed
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && backward[at(k - 1)] < backward[at(k + 1)]) {
+                backward[at(k + 1)]
+            } else {
+                backward[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[(n - x - 1) as usize] == b[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[at(k)] = x;
+
+            let c = delta - k;
+            if !odd && (-d..=d).contains(&c) && x + forward[at(c)] >= n {
+                return (
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - x0) as usize,
+                    (m - y0) as usize,
+                );
+            }
+        }
+    }
+    unreachable!("the searches meet within (n + m) / 2 steps")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_diff() {
+        let diff = line_diff("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(diff, "@@ -1,3 +1,3 @@\n a\n-b\n c\n+d\n");
+        assert_eq!(line_diff("x\n", "x\n"), "");
+        assert_eq!(line_diff("", "x\n"), "@@ -0,0 +1,1 @@\n+x\n");
+    }
+
+    #[test]
+    fn test_line_diff_only_shows_changed_hunks() {
+        let old: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+        let new = old
+            .replace("line 10\n", "line ten\n")
+            .replace("line 80\n", "");
+        let diff = line_diff(&old, &new);
+
+        assert_eq!(
+            diff,
+            "@@ -8,7 +8,7 @@\n line 7\n line 8\n line 9\n-line 10\n+line ten\n line 11\n line 12\n line 13\n\
+             @@ -78,7 +78,6 @@\n line 77\n line 78\n line 79\n-line 80\n line 81\n line 82\n line 83\n"
+        );
+    }
+
+    #[test]
+    fn test_line_diff_is_minimal() {
+        // Applying the edit script must give back `new`, using as few
+        // changed lines as the LCS allows
+        let old = "a\nb\nc\na\nb\nb\na\n";
+        let new = "c\nb\na\nb\na\nc\n";
+        let diff = line_diff(old, new);
+        let changed = diff
+            .lines()
+            .filter(|l| !l.starts_with("@@") && !l.starts_with(' '))
+            .count();
+        assert_eq!(changed, 5, "diff: {}", diff);
+
+        let rebuilt: Vec<&str> = diff
+            .lines()
+            .filter(|l| l.starts_with(' ') || l.starts_with('+'))
+            .map(|l| &l[1..])
+            .collect();
+        assert_eq!(rebuilt.join("\n") + "\n", new);
+    }
+}
@@ -1,3 +1,6 @@
+use std::time::Duration;
+use unicode_width::UnicodeWidthChar;
+
 /// Format bytes into human-readable size
 pub fn format_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
@@ -16,6 +19,44 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+/// Parse a human size like `512`, `500K`, `2M` or `1G` (binary units) into bytes
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let trimmed = s.trim();
+    let upper = trimmed.to_ascii_uppercase();
+    let upper = upper.strip_suffix('B').unwrap_or(&upper);
+    let (digits, multiplier) = match upper.chars().last() {
+        Some('K') => (&upper[..upper.len() - 1], 1024),
+        Some('M') => (&upper[..upper.len() - 1], 1024 * 1024),
+        Some('G') => (&upper[..upper.len() - 1], 1024 * 1024 * 1024),
+        _ => (upper, 1),
+    };
+    digits
+        .trim()
+        .parse::<u64>()
+        .map(|n| n * multiplier)
+        .map_err(|_| format!("invalid size '{}' (expected e.g. 512, 500K, 2M)", trimmed))
+}
+
+/// Parse a duration like `90s`, `30m`, `12h`, `7d` or `2w`
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let trimmed = s.trim();
+    let err = || format!("invalid duration '{}' (expected e.g. 12h, 7d, 2w)", trimmed);
+    let unit = trimmed.chars().last().ok_or_else(err)?;
+    let seconds = match unit.to_ascii_lowercase() {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return Err(err()),
+    };
+    trimmed[..trimmed.len() - 1]
+        .trim()
+        .parse::<u64>()
+        .map(|n| Duration::from_secs(n * seconds))
+        .map_err(|_| err())
+}
+
 /// Size badge for intuitive classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -51,7 +92,6 @@ pub struct SizeMark {
 }
 
 /// Get size tick mark only (no badge)
-#[allow(dead_code)]
 pub fn size_mark(bytes: u64) -> SizeMark {
     let human = format_size(bytes);
 
@@ -142,10 +182,104 @@ pub fn is_global_outlier(loc: usize, threshold: usize) -> bool {
     loc >= threshold
 }
 
+/// Shorten a name to at most `max` characters with a middle ellipsis,
+/// keeping the tail so extensions stay visible (`verylongna…e.rs`).
+/// A character counts together with the combining marks and joiners that
+/// follow it, so an accent is never split from its letter.
+pub fn truncate_name(name: &str, max: Option<usize>) -> String {
+    let clusters = char_clusters(name);
+    let max = match max {
+        Some(max) if clusters.len() > max => max,
+        _ => return name.to_string(),
+    };
+    if max == 0 {
+        return String::new();
+    }
+
+    let tail = (max - 1) / 3;
+    let head = max - 1 - tail;
+    let mut out: String = clusters[..head].concat();
+    out.push('…');
+    out.push_str(&clusters[clusters.len() - tail..].concat());
+    out
+}
+
+/// Split text into user-perceived characters: a base character plus any
+/// zero-width marks after it, with `ZWJ` gluing emoji sequences together
+fn char_clusters(text: &str) -> Vec<&str> {
+    const ZWJ: char = '\u{200d}';
+    let mut clusters: Vec<&str> = Vec::new();
+    let mut start = 0;
+    let mut prev = None;
+    for (i, c) in text.char_indices() {
+        let joins = c.width() == Some(0) || prev == Some(ZWJ);
+        if i > 0 && !joins {
+            clusters.push(&text[start..i]);
+            start = i;
+        }
+        prev = Some(c);
+    }
+    if start < text.len() {
+        clusters.push(&text[start..]);
+    }
+    clusters
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512"), Ok(512));
+        assert_eq!(parse_size("2K"), Ok(2048));
+        assert_eq!(parse_size("1kb"), Ok(1024));
+        assert_eq!(parse_size("3M"), Ok(3 * 1024 * 1024));
+        assert_eq!(parse_size("1G"), Ok(1024 * 1024 * 1024));
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_duration("12h"), Ok(Duration::from_secs(12 * 3600)));
+        assert_eq!(parse_duration("7D"), Ok(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse_duration("2w"), Ok(Duration::from_secs(14 * 86400)));
+        assert!(parse_duration("7").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn test_truncate_name() {
+        assert_eq!(truncate_name("short.rs", Some(15)), "short.rs");
+        assert_eq!(truncate_name("short.rs", None), "short.rs");
+        assert_eq!(
+            truncate_name("verylongname_for_a_file.rs", Some(15)),
+            "verylongna…e.rs"
+        );
+        assert_eq!(truncate_name("abcdef", Some(1)), "…");
+        assert_eq!(
+            truncate_name("日本語のファイル名.txt", Some(7)),
+            "日本語の…xt"
+        );
+    }
+
+    #[test]
+    fn test_truncate_name_keeps_combining_marks() {
+        // `é` spelled as `e` + U+0301 counts as one character
+        let name = "re\u{301}sume\u{301}_final.txt";
+        assert_eq!(truncate_name(name, Some(20)), name);
+        assert_eq!(truncate_name(name, Some(7)), "re\u{301}su…xt");
+        assert_eq!(truncate_name("abcdefghe\u{301}", Some(4)), "ab…e\u{301}");
+
+        // A ZWJ emoji sequence stays whole
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        assert_eq!(char_clusters(&format!("a{}b", family)).len(), 3);
+    }
+
     #[test]
     fn test_format_size() {
         assert_eq!(format_size(0), "0 B");
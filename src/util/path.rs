@@ -7,6 +7,22 @@ pub fn calculate_display_path(resolved_path: &Path, display_root: &Path) -> Path
     pathdiff::diff_paths(resolved_path, display_root).unwrap_or_else(|| resolved_path.to_path_buf())
 }
 
+/// Deepest directory containing every path (all absolute, e.g. canonicalized)
+pub fn common_ancestor(paths: &[PathBuf]) -> PathBuf {
+    let mut ancestor = match paths.first() {
+        Some(first) => first.clone(),
+        None => return PathBuf::new(),
+    };
+    for path in &paths[1..] {
+        while !path.starts_with(&ancestor) {
+            if !ancestor.pop() {
+                break;
+            }
+        }
+    }
+    ancestor
+}
+
 /// Normalize a path string (remove ./, //, etc)
 #[cfg(test)]
 pub fn normalize_path_string(path: &str) -> String {
@@ -83,6 +99,25 @@ mod tests {
         assert_eq!(normalize_path_string("./.."), "..");
     }
 
+    #[test]
+    fn test_common_ancestor() {
+        let paths = [
+            PathBuf::from("/repo/src/util"),
+            PathBuf::from("/repo/src/main"),
+            PathBuf::from("/repo/docs"),
+        ];
+        assert_eq!(common_ancestor(&paths), PathBuf::from("/repo"));
+        assert_eq!(common_ancestor(&paths[..2]), PathBuf::from("/repo/src"));
+        assert_eq!(
+            common_ancestor(&[PathBuf::from("/repo/src")]),
+            PathBuf::from("/repo/src")
+        );
+        assert_eq!(
+            common_ancestor(&[PathBuf::from("/a"), PathBuf::from("/b")]),
+            PathBuf::from("/")
+        );
+    }
+
     #[test]
     fn test_calculate_display_path() {
         let resolved = PathBuf::from("/home/user/project/src/main.rs");
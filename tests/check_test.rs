@@ -0,0 +1,47 @@
+mod fixtures;
+
+use fixtures::{p, run_tree2md, FixtureBuilder};
+use std::fs;
+
+#[test]
+fn test_check_matches_snapshot() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("README.md", "# Test\n")
+        .build();
+    let snap_dir = tempfile::TempDir::new().unwrap();
+    let snapshot = snap_dir.path().join("tree.snap");
+
+    let (output, _, success) = run_tree2md([p(&root)]);
+    assert!(success);
+    fs::write(&snapshot, &output).unwrap();
+
+    let (output, stderr, success) = run_tree2md([p(&root), "--check".into(), p(&snapshot)]);
+    assert!(success, "identical output should pass: {}", stderr);
+    assert!(output.is_empty(), "--check should not print the tree");
+}
+
+#[test]
+fn test_check_fails_on_drift() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+    let snap_dir = tempfile::TempDir::new().unwrap();
+    let snapshot = snap_dir.path().join("tree.snap");
+
+    let (output, _, _) = run_tree2md([p(&root)]);
+    fs::write(&snapshot, &output).unwrap();
+
+    fs::write(root.join("src/lib.rs"), "pub fn lib() {}\n").unwrap();
+
+    let (output, stderr, success) = run_tree2md([p(&root), "--check".into(), p(&snapshot)]);
+    assert!(!success, "changed tree should fail the check");
+    assert!(output.is_empty());
+    assert!(
+        stderr
+            .lines()
+            .any(|l| l.starts_with('+') && l.contains("lib.rs")),
+        "diff should show the added file: {}",
+        stderr
+    );
+}
@@ -1,6 +1,6 @@
 mod fixtures;
 
-use fixtures::{p, run_tree2md, FixtureBuilder};
+use fixtures::{p, run_tree2md, run_tree2md_with_env, FixtureBuilder};
 
 #[test]
 fn test_emoji_disabled() {
@@ -43,3 +43,60 @@ fn test_pipe_mode_no_emoji() {
     assert!(output.contains("main.rs"));
     assert!(output.contains("script.py"));
 }
+
+#[test]
+fn test_emoji_directory_name_key() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .file("docs/guide.md", "# Guide")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--output".into(),
+        "tty".into(),
+        "--fun".into(),
+        "on".into(),
+        "--emoji".into(),
+        "src/=📦".into(),
+    ]);
+    assert!(success);
+
+    let src_line = output.lines().find(|l| l.contains("src/")).unwrap();
+    assert!(src_line.contains("📦"), "src should be themed: {}", output);
+    let docs_line = output.lines().find(|l| l.contains("docs/")).unwrap();
+    assert!(!docs_line.contains("📦"), "docs keeps default: {}", output);
+}
+
+#[test]
+fn test_emoji_map_env_fallback() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .build();
+    let args = || {
+        [
+            p(&root),
+            "--output".into(),
+            "tty".into(),
+            "--fun".into(),
+            "on".into(),
+        ]
+    };
+
+    let (output, _, success) =
+        run_tree2md_with_env(args(), &[("TREE2MD_EMOJI_MAP", ".rs=🚀;src/=📦")]);
+    assert!(success);
+    let main_line = output.lines().find(|l| l.contains("main.rs")).unwrap();
+    assert!(main_line.contains("🚀"), "output: {}", output);
+    let src_line = output.lines().find(|l| l.contains("src/")).unwrap();
+    assert!(src_line.contains("📦"), "output: {}", output);
+
+    // An explicit --emoji-map file wins over the environment
+    let map = root.join("emoji.toml");
+    std::fs::write(&map, "[extensions]\nrs = \"🦀\"\n").unwrap();
+    let mut with_file: Vec<String> = args().into();
+    with_file.extend(["--emoji-map".into(), p(&map)]);
+    let (output, _, success) = run_tree2md_with_env(with_file, &[("TREE2MD_EMOJI_MAP", ".rs=🚀")]);
+    assert!(success);
+    assert!(!output.contains("🚀"), "output: {}", output);
+}
@@ -123,3 +123,21 @@ fn test_exclude_dotfile_pattern() {
         "Should not include .gitignore"
     );
 }
+
+#[test]
+fn test_exclude_generated_from_gitattributes() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file(".gitattributes", "*.pb.go linguist-generated\n")
+        .file("api/service.pb.go", "package api\n")
+        .file("api/service.go", "package api\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root)]);
+    assert!(success);
+    assert!(output.contains("service.pb.go"), "kept without the flag");
+
+    let (output, _, success) = run_tree2md([p(&root), "--exclude-generated".into()]);
+    assert!(success);
+    assert!(!output.contains("service.pb.go"), "output: {}", output);
+    assert!(output.contains("service.go"));
+}
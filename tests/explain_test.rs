@@ -0,0 +1,62 @@
+mod fixtures;
+
+use fixtures::{p, run_tree2md, FixtureBuilder};
+
+#[test]
+fn test_explain_gitignored_and_included() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file(".gitignore", "*.tmp\n")
+        .file("scratch.tmp", "x")
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--use-gitignore".into(),
+        "always".into(),
+        "--explain".into(),
+        "scratch.tmp".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("scratch.tmp: matched gitignore rule '*.tmp' -> Exclude"),
+        "output: {}",
+        output
+    );
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "-I".into(),
+        "*.rs".into(),
+        "--explain".into(),
+        "src/main.rs".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("src/main.rs: matched include glob '**/*.rs' -> Include"),
+        "output: {}",
+        output
+    );
+    // The explanation replaces the tree
+    assert!(!output.contains("└──"), "output: {}", output);
+}
+
+#[test]
+fn test_explain_reports_pruned_ancestor() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("node_modules/pkg/index.js", "x")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--explain".into(),
+        "node_modules/pkg/index.js".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("node_modules/: matched safety rule 'node_modules/**' -> PruneDir"),
+        "output: {}",
+        output
+    );
+    assert!(output.contains("not reached"), "output: {}", output);
+}
@@ -289,3 +289,433 @@ fn test_complex_precedence_scenario() {
     // Normal files included
     assert!(output.contains("main.rs"));
 }
+
+#[test]
+fn test_only_dirs_shows_skeleton() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .file("src/render/pipe.rs", "// pipe")
+        .file("docs/guide.md", "# Guide")
+        .file("README.md", "# Test")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--only-dirs".into()]);
+    assert!(success);
+
+    // Directory hierarchy remains
+    assert!(output.contains("src/"));
+    assert!(output.contains("render/"));
+    assert!(output.contains("docs/"));
+
+    // No file lines
+    assert!(!output.contains("main.rs"), "got:\n{}", output);
+    assert!(!output.contains("pipe.rs"));
+    assert!(!output.contains("guide.md"));
+    assert!(!output.contains("README.md"));
+
+    // Stats report no files
+    assert!(output.contains("0 files"), "got:\n{}", output);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_show_symlinks_lists_link_targets() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("real.txt", "hello\n")
+        .file("dir/inner.txt", "inner\n")
+        .build();
+    std::os::unix::fs::symlink("real.txt", root.join("link.txt")).unwrap();
+    std::os::unix::fs::symlink("dir", root.join("dirlink")).unwrap();
+
+    // Default: symlinks are skipped
+    let (output, _, success) = run_tree2md([p(&root)]);
+    assert!(success);
+    assert!(!output.contains("link.txt"));
+    assert!(!output.contains("dirlink"));
+
+    let (output, _, success) = run_tree2md([p(&root), "--show-symlinks".into()]);
+    assert!(success);
+    assert!(
+        output.contains("link.txt -> real.txt"),
+        "output: {}",
+        output
+    );
+    assert!(output.contains("dirlink -> dir"), "output: {}", output);
+    // Linked directories are not descended into
+    assert_eq!(output.matches("inner.txt").count(), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_follow_symlinks_detects_cycles() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("a/in_a.txt", "a\n")
+        .file("b/in_b.txt", "b\n")
+        .build();
+    // a/to_b -> b and b/to_a -> a form a cycle
+    std::os::unix::fs::symlink("../b", root.join("a/to_b")).unwrap();
+    std::os::unix::fs::symlink("../a", root.join("b/to_a")).unwrap();
+
+    let (output, stderr, success) = run_tree2md([p(&root), "--follow-symlinks".into()]);
+    assert!(success);
+    assert!(
+        stderr.contains("skipping symlink cycle at"),
+        "stderr: {}",
+        stderr
+    );
+    // Followed link contents appear under the link name
+    assert!(output.contains("to_b/"), "output: {}", output);
+    assert!(
+        output.matches("in_b.txt").count() >= 2,
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_exclude_dot_dirs_keeps_dot_files() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file(".github/workflows/ci.yml", "on: push\n")
+        .file(".vscode/settings.json", "{}\n")
+        .file(".gitignore", "target/\n")
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--exclude-dot-dirs".into()]);
+    assert!(success);
+    assert!(!output.contains(".github"), "output: {}", output);
+    assert!(!output.contains(".vscode"), "output: {}", output);
+    assert!(output.contains(".gitignore"), "output: {}", output);
+    assert!(output.contains("main.rs"), "output: {}", output);
+}
+
+#[test]
+fn test_max_children_per_dir() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("d.txt", "d\n")
+        .file("a.txt", "a\n")
+        .file("c.txt", "c\n")
+        .file("b/inner1.txt", "x\n")
+        .file("b/inner2.txt", "x\n")
+        .file("b/inner3.txt", "x\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--max-children-per-dir".into(), "2".into()]);
+    assert!(success);
+
+    // Root keeps a.txt and b/ (alphabetically first), notes c.txt and d.txt
+    assert!(output.contains("a.txt"), "output: {}", output);
+    assert!(output.contains("b/"), "output: {}", output);
+    assert!(!output.contains("c.txt"), "output: {}", output);
+    assert!(!output.contains("d.txt"), "output: {}", output);
+    assert!(output.contains("└── … 2 more\n"), "output: {}", output);
+
+    // The cap applies to each directory on its own
+    assert!(output.contains("inner1.txt"), "output: {}", output);
+    assert!(output.contains("inner2.txt"), "output: {}", output);
+    assert!(!output.contains("inner3.txt"), "output: {}", output);
+    assert!(output.contains("│   └── … 1 more\n"), "output: {}", output);
+}
+
+#[test]
+fn test_exclude_submodules_lists_dir_as_leaf() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file(
+            ".gitmodules",
+            "[submodule \"vendor/lib\"]\n\tpath = vendor/lib\n\turl = https://example.com/lib.git\n",
+        )
+        .file("vendor/lib/.git", "gitdir: ../../.git/modules/vendor/lib\n")
+        .file("vendor/lib/src/inner.rs", "fn inner() {}\n")
+        .file("vendor/other.rs", "fn other() {}\n")
+        .file("main.rs", "fn main() {}\n")
+        .build();
+
+    let (output, _, success) =
+        run_tree2md([p(&root), "--unsafe".into(), "--exclude-submodules".into()]);
+    assert!(success);
+    assert!(output.contains("lib/"), "output: {}", output);
+    assert!(!output.contains("inner.rs"), "output: {}", output);
+    assert!(output.contains("other.rs"), "output: {}", output);
+
+    // Without the flag the submodule is pruned like any nested repository
+    let (output, _, _) = run_tree2md([p(&root), "--unsafe".into()]);
+    assert!(!output.contains("lib/"), "output: {}", output);
+    assert!(!output.contains("inner.rs"), "output: {}", output);
+}
+
+#[test]
+fn test_prune_dirs_over_collapses_large_dirs() {
+    let mut builder = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("src/lib.rs", "pub fn lib() {}\n");
+    for i in 0..1000 {
+        builder = builder.file(format!("third_party/gen_{:04}.c", i), "int x;\n");
+    }
+    let (_tmp, root) = builder.build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--prune-dirs-over".into(), "100".into()]);
+    assert!(success);
+    assert!(
+        output.contains("third_party/  (1000 files, collapsed)"),
+        "output: {}",
+        output
+    );
+    assert!(!output.contains("gen_0000.c"), "output: {}", output);
+
+    // Small directories stay expanded
+    assert!(output.contains("main.rs"), "output: {}", output);
+    assert!(output.contains("lib.rs"), "output: {}", output);
+}
+
+#[test]
+fn test_fail_on_empty() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+
+    let (output, stderr, success) = run_tree2md([
+        p(&root),
+        "-I".into(),
+        "*.py".into(),
+        "--fail-on-empty".into(),
+    ]);
+    assert!(!success, "output: {}", output);
+    assert!(stderr.contains("no files to show"), "stderr: {}", stderr);
+
+    // Without the flag an empty tree is still a success
+    let (_, _, success) = run_tree2md([p(&root), "-I".into(), "*.py".into()]);
+    assert!(success);
+
+    // And the flag is quiet when something matched
+    let (_, _, success) = run_tree2md([
+        p(&root),
+        "-I".into(),
+        "*.rs".into(),
+        "--fail-on-empty".into(),
+    ]);
+    assert!(success);
+}
+
+#[test]
+fn test_fail_on_empty_after_loc_filter() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+
+    // The walk finds main.rs, but --min-loc drops it before rendering
+    let (_, stderr, success) = run_tree2md([
+        p(&root),
+        "--min-loc".into(),
+        "100".into(),
+        "--fail-on-empty".into(),
+    ]);
+    assert!(!success);
+    assert!(stderr.contains("no files to show"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_max_path_length_skips_long_paths() {
+    let long_dir = "d".repeat(40);
+    let long_file = format!("{}/{}.txt", long_dir, "f".repeat(40));
+    let (_tmp, root) = FixtureBuilder::new()
+        .file(&long_file, "x")
+        .file("short.txt", "y")
+        .build();
+
+    let (output, stderr, success) = run_tree2md([
+        p(&root),
+        "--max-path-length".into(),
+        "60".into(),
+        "--show-ignored-count".into(),
+    ]);
+    assert!(success);
+
+    assert!(output.contains("short.txt"));
+    assert!(output.contains(&long_dir), "Parent fits the limit");
+    assert!(!output.contains("fffff"), "output: {}", output);
+    assert!(
+        stderr.contains("Warning: skipping path longer than 60 characters"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("Hidden: 1 too long"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_summarize_known_dirs() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .files_with(
+            (0..142).map(|i| format!("node_modules/pkg{}/f{}.js", i % 7, i)),
+            |_| String::new(),
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    let (output, _, success) =
+        run_tree2md([p(&root), "--unsafe".into(), "--summarize-known-dirs".into()]);
+    assert!(success);
+    assert!(
+        output.contains("node_modules/  (142 files, collapsed)\n"),
+        "output: {}",
+        output
+    );
+    assert!(!output.contains("pkg0"), "Summarized dirs are leaves");
+
+    // In safe mode the directory is summarized instead of silently pruned
+    let (output, _, _) = run_tree2md([p(&root), "--summarize-known-dirs".into()]);
+    assert!(output.contains("node_modules/  (142 files, collapsed)"));
+    let (output, _, _) = run_tree2md([p(&root)]);
+    assert!(!output.contains("node_modules"));
+
+    // --known-dir replaces the built-in set
+    let (output, _, _) = run_tree2md([
+        p(&root),
+        "--unsafe".into(),
+        "--summarize-known-dirs".into(),
+        "--known-dir".into(),
+        "src".into(),
+    ]);
+    assert!(
+        output.contains("src/  (1 files, collapsed)"),
+        "output: {}",
+        output
+    );
+    assert!(output.contains("pkg0/"), "output: {}", output);
+}
+
+#[test]
+fn test_modified_window() {
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("fresh.txt", "new")
+        .file("recent.txt", "week-old")
+        .file("stale.txt", "ancient")
+        .build();
+
+    let day = Duration::from_secs(24 * 60 * 60);
+    let age = |name: &str, days: u32| {
+        File::options()
+            .write(true)
+            .open(root.join(name))
+            .unwrap()
+            .set_modified(SystemTime::now() - day * days)
+            .unwrap();
+    };
+    age("recent.txt", 7);
+    age("stale.txt", 90);
+
+    let (output, stderr, success) = run_tree2md([
+        p(&root),
+        "--older-than".into(),
+        "1d".into(),
+        "--modified-since".into(),
+        "30d".into(),
+        "--show-ignored-count".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("recent.txt"), "output: {}", output);
+    assert!(!output.contains("fresh.txt"), "Too new: {}", output);
+    assert!(!output.contains("stale.txt"), "Too old: {}", output);
+    assert!(
+        stderr.contains("Hidden: 2 outside date range"),
+        "stderr: {}",
+        stderr
+    );
+
+    // Either bound alone
+    let (output, _, _) = run_tree2md([p(&root), "--older-than".into(), "30d".into()]);
+    assert!(output.contains("stale.txt") && !output.contains("recent.txt"));
+    let (output, _, _) = run_tree2md([p(&root), "--modified-since".into(), "1d".into()]);
+    assert!(output.contains("fresh.txt") && !output.contains("recent.txt"));
+}
+
+#[test]
+fn test_sample_is_reproducible() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .files_with(
+            (0..40).map(|i| format!("dir{}/file{}.txt", i % 4, i)),
+            |_| "x".to_string(),
+        )
+        .build();
+
+    let sample = |seed: &str| {
+        let (output, _, success) = run_tree2md([
+            p(&root),
+            "--only-files".into(),
+            "--sample".into(),
+            "5".into(),
+            "--seed".into(),
+            seed.into(),
+        ]);
+        assert!(success);
+        output
+    };
+
+    let first = sample("7");
+    assert_eq!(first.lines().count(), 5, "output: {}", first);
+    assert_eq!(first, sample("7"), "Same seed, same subset");
+    assert_ne!(first, sample("8"), "Different seed, different subset");
+}
+
+#[test]
+fn test_exclude_zero_loc() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("src/empty.rs", "")
+        .file("placeholder/.keep", "")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--exclude-zero-loc".into(),
+        "--loc".into(),
+        "fast".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("main.rs"), "output: {}", output);
+    assert!(!output.contains("empty.rs"), "output: {}", output);
+    assert!(
+        !output.contains("placeholder"),
+        "Emptied dirs are pruned: {}",
+        output
+    );
+
+    // Without the flag, empty files stay
+    let (output, _, _) = run_tree2md([p(&root), "--loc".into(), "fast".into()]);
+    assert!(output.contains("empty.rs"));
+}
+
+#[test]
+fn test_min_max_loc() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/tiny.rs", "fn a() {}\n")
+        .file("src/medium.rs", "let x = 1;\n".repeat(20))
+        .file("src/big.rs", "let x = 1;\n".repeat(200))
+        .file("small/only.rs", "fn b() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--min-loc".into(),
+        "10".into(),
+        "--max-loc".into(),
+        "100".into(),
+        "--loc".into(),
+        "fast".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("medium.rs"), "output: {}", output);
+    assert!(!output.contains("tiny.rs"), "output: {}", output);
+    assert!(!output.contains("big.rs"), "output: {}", output);
+    assert!(
+        !output.contains("small/"),
+        "Emptied dirs are pruned: {}",
+        output
+    );
+
+    let (output, _, _) = run_tree2md([p(&root), "--min-loc".into(), "100".into()]);
+    assert!(output.contains("big.rs"), "output: {}", output);
+    assert!(!output.contains("medium.rs"), "output: {}", output);
+}
@@ -10,8 +10,17 @@ where
     I: IntoIterator<Item = S>,
     S: AsRef<std::ffi::OsStr>,
 {
-    let mut cmd = Command::cargo_bin("tree2md").expect("tree2md binary not found");
-    cmd.args(args);
+    run_tree2md_with_env(args, &[])
+}
+
+/// Run tree2md with extra environment variables set
+pub fn run_tree2md_with_env<I, S>(args: I, envs: &[(&str, &str)]) -> (String, String, bool)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("tree2md"));
+    cmd.args(args).envs(envs.iter().copied());
 
     let Output {
         status,
@@ -93,6 +102,12 @@ impl FixtureBuilder {
     }
 }
 
+impl Default for FixtureBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// DSL for defining directory structures declaratively
 #[derive(Debug)]
 pub enum FsEntry {
@@ -0,0 +1,77 @@
+mod fixtures;
+
+use fixtures::{p, run_tree2md, FixtureBuilder};
+
+#[test]
+fn test_only_files_sorted_list() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .file("src/lib.rs", "pub fn lib() {}")
+        .file("README.md", "# Test")
+        .file("docs/guide.md", "# Guide")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--only-files".into()]);
+    assert!(success);
+
+    assert_eq!(
+        output, "README.md\ndocs/guide.md\nsrc/lib.rs\nsrc/main.rs\n",
+        "Should be a sorted newline-delimited list of relative paths"
+    );
+}
+
+#[test]
+fn test_only_files_respects_filters() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .file("src/notes.txt", "notes")
+        .file("README.md", "# Test")
+        .build();
+
+    let (output, _, success) =
+        run_tree2md([p(&root), "--only-files".into(), "-I".into(), "*.rs".into()]);
+    assert!(success);
+
+    assert_eq!(output, "src/main.rs\n");
+}
+
+#[test]
+fn test_only_files_loc_filters_still_apply() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("empty.rs", "")
+        .file("short.rs", "fn a() {}\n")
+        .file("long.rs", "fn a() {}\nfn b() {}\nfn c() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--only-files".into()]);
+    assert!(success);
+    assert_eq!(output, "empty.rs\nlong.rs\nshort.rs\n");
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--only-files".into(),
+        "--min-loc".into(),
+        "2".into(),
+    ]);
+    assert!(success);
+    assert_eq!(output, "long.rs\n");
+
+    let (output, _, success) =
+        run_tree2md([p(&root), "--only-files".into(), "--exclude-zero-loc".into()]);
+    assert!(success);
+    assert_eq!(output, "long.rs\nshort.rs\n");
+}
+
+#[test]
+fn test_print0_nul_separated() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .file("README.md", "# Test")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--print0".into()]);
+    assert!(success);
+
+    assert_eq!(output, "README.md\0src/main.rs\0");
+    assert!(!output.contains('\n'), "No newlines with --print0");
+}
@@ -90,6 +90,35 @@ fn test_auto_detection_from_subdirectory() {
     );
 }
 
+/// --ignore-parent-gitignore scopes ignores to the target and below.
+#[test]
+fn test_ignore_parent_gitignore() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .dir(".git")
+        .file(".gitignore", "*.secret\n")
+        .file("sub/.gitignore", "*.tmp\n")
+        .file("sub/visible.txt", "visible")
+        .file("sub/kept.secret", "secret")
+        .file("sub/scratch.tmp", "tmp")
+        .build();
+
+    let (output, _, success) =
+        run_tree2md([p(root.join("sub")), "--ignore-parent-gitignore".into()]);
+    assert!(success);
+
+    assert!(output.contains("visible.txt"));
+    assert!(
+        output.contains("kept.secret"),
+        "Parent .gitignore should not apply: {}",
+        output
+    );
+    assert!(
+        !output.contains("scratch.tmp"),
+        "The target's own .gitignore still applies: {}",
+        output
+    );
+}
+
 /// Include patterns (-I) should override nested .gitignore.
 #[test]
 fn test_include_overrides_nested_gitignore() {
@@ -147,3 +176,123 @@ fn test_never_mode_ignores_nested_gitignore() {
     );
     assert!(output.contains("file.txt"));
 }
+
+#[test]
+fn test_include_gitignored_marks_ignored_entries() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .dir(".git")
+        .file(".gitignore", "*.log\nout/\n")
+        .file("app.txt", "app")
+        .file("debug.log", "log")
+        .file("out/artifact.bin", "bin")
+        .build();
+
+    // Default: ignored entries are dropped
+    let (output, _, success) = run_tree2md([p(&root)]);
+    assert!(success);
+    assert!(!output.contains("debug.log"));
+    assert!(!output.contains("(ignored)"));
+
+    let (output, _, success) = run_tree2md([p(&root), "--include-gitignored".into()]);
+    assert!(success);
+    assert!(
+        output.contains("debug.log  (1 lines)  (ignored)"),
+        "output: {}",
+        output
+    );
+    assert!(output.contains("out/  (ignored)"), "output: {}", output);
+    assert!(!output.contains("app.txt  (ignored)"));
+    // Ignored directories are not descended into
+    assert!(!output.contains("artifact.bin"));
+}
+
+#[test]
+fn test_show_ignored_count() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .dir(".git")
+        .file(".gitignore", "*.log\nout/\n")
+        .file("app.txt", "app")
+        .file("debug.log", "log")
+        .file("trace.log", "log")
+        .file("out/artifact.bin", "bin")
+        .file(".env", "SECRET=1")
+        .file("server.pem", "key")
+        .file("scratch.tmp", "tmp")
+        .build();
+
+    let (output, stderr, success) = run_tree2md([
+        p(&root),
+        "--show-ignored-count".into(),
+        "-X".into(),
+        "*.tmp".into(),
+    ]);
+    assert!(success);
+    // The pruned out/ directory counts once, not per file inside it
+    assert!(
+        stderr.contains("Hidden: 3 gitignored, 2 safety, 1 excluded"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!output.contains("Hidden:"), "Summary stays off stdout");
+
+    // Off by default
+    let (_, stderr, _) = run_tree2md([p(&root)]);
+    assert!(!stderr.contains("Hidden:"));
+}
+
+#[test]
+fn test_respect_ignore_files() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file(".ignore", "scratch/\n*.bak\n")
+        .file("scratch/notes.txt", "x")
+        .file("src/main.rs", "fn main() {}")
+        .file("src/old.bak", "x")
+        .file("src/nested/.ignore", "local.txt\n")
+        .file("src/nested/local.txt", "x")
+        .file("local.txt", "x")
+        .build();
+
+    // Without the flag, .ignore files are just files
+    let (output, _, success) = run_tree2md([p(&root)]);
+    assert!(success);
+    assert!(output.contains("scratch/"), "output: {}", output);
+    assert!(output.contains("old.bak"), "output: {}", output);
+
+    let (output, _, success) = run_tree2md([p(&root), "--respect-ignore".into()]);
+    assert!(success);
+    assert!(!output.contains("scratch/"), "output: {}", output);
+    assert!(!output.contains("old.bak"), "output: {}", output);
+    assert!(output.contains("main.rs"), "output: {}", output);
+
+    // Nested .ignore files only apply below their own directory
+    assert_eq!(output.matches("local.txt").count(), 1, "output: {}", output);
+}
+
+/// --extra-ignore loads any gitignore-syntax file, anchored at the target.
+#[test]
+fn test_extra_ignore_file() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("docs/guide.md", "# Guide")
+        .file("docs/generated/api.md", "# API")
+        .file("src/main.rs", "fn main() {}")
+        .file("shared/docs.ignore", "/docs/generated/\n*.rs\n")
+        .build();
+
+    let (output, stderr, success) = run_tree2md([
+        p(&root),
+        "--extra-ignore".into(),
+        p(root.join("shared/docs.ignore")),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(output.contains("guide.md"), "output: {}", output);
+    assert!(!output.contains("generated"), "output: {}", output);
+    assert!(!output.contains("main.rs"), "output: {}", output);
+
+    let (_, stderr, success) = run_tree2md([
+        p(&root),
+        "--extra-ignore".into(),
+        p(root.join("missing.ignore")),
+    ]);
+    assert!(!success);
+    assert!(stderr.contains("missing.ignore"), "stderr: {}", stderr);
+}
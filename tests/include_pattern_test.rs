@@ -83,3 +83,61 @@ fn test_include_directory_name_pattern() {
         "Should not include README.md"
     );
 }
+
+#[test]
+fn test_include_extension_prunes_empty_branches() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/lib.rs", "pub fn lib() {}")
+        .file("src/nested/deep/mod.rs", "pub mod x;")
+        .file("docs/guide.md", "# Guide")
+        .file("docs/api/v1/reference/index.md", "# Ref")
+        .file("src/nested/assets/logo/readme.txt", "logo")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "-I".into(), "*.rs".into()]);
+    assert!(success);
+
+    // Dirs leading to matches survive
+    assert!(output.contains("src/"), "output: {}", output);
+    assert!(output.contains("deep/"), "output: {}", output);
+    assert!(output.contains("mod.rs"), "output: {}", output);
+
+    // Branches with no matches disappear entirely, however deep the chain
+    for gone in ["docs/", "api/", "v1/", "reference/", "assets/", "logo/"] {
+        assert!(
+            !output.contains(gone),
+            "{} should be pruned: {}",
+            gone,
+            output
+        );
+    }
+}
+
+#[test]
+fn test_empty_directory_chain_collapses_in_one_pass() {
+    // outer/middle/inner/ holds only a file the exclude drops and nothing
+    // else, so the whole chain empties bottom-up in a single removal pass
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("outer/middle/inner/generated.rs", "// generated")
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "-I".into(),
+        "*.rs".into(),
+        "-X".into(),
+        "generated.rs".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("main.rs"), "output: {}", output);
+
+    for gone in ["outer/", "middle/", "inner/", "generated.rs"] {
+        assert!(
+            !output.contains(gone),
+            "{} should be pruned: {}",
+            gone,
+            output
+        );
+    }
+}
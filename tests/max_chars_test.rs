@@ -160,3 +160,119 @@ fn test_contents_mode_default_is_head() {
     // Head mode keeps from the beginning
     assert!(output.contains("line1"));
 }
+
+#[test]
+fn test_custom_truncation_note() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("a.txt", "a1\na2\na3\na4\na5\n")
+        .file("b.txt", "b1\nb2\nb3\nb4\nb5\n")
+        .build();
+
+    // Same budget as test_max_chars_head_uniform_n: n=2 for both files
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "-c".into(),
+        "--max-chars".into(),
+        "12".into(),
+        "--truncation-note".into(),
+        "// [Content truncated: showing first {shown} of {total} lines, {omitted} omitted]".into(),
+    ]);
+    assert!(success);
+
+    assert_eq!(
+        output
+            .matches("// [Content truncated: showing first 2 of 5 lines, 3 omitted]")
+            .count(),
+        2,
+        "Custom note should render with substituted counts: {}",
+        output
+    );
+    assert!(!output.contains("lines omitted)"));
+}
+
+#[test]
+fn test_exclude_larger_than_skips_content() {
+    let big = "x".repeat(4096);
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("small.txt", "tiny content")
+        .file("big.txt", &big)
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "-c".into(),
+        "--exclude-larger-than".into(),
+        "1K".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("tiny content"));
+    assert!(
+        !output.contains("big.txt"),
+        "oversized file should be dropped"
+    );
+    assert!(!output.contains(&big));
+}
+
+#[test]
+fn test_exclude_larger_than_goes_through_matcher() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("small.txt", "tiny content")
+        .file("big.txt", "x".repeat(4096))
+        .build();
+
+    let (_, stderr, success) = run_tree2md([
+        p(&root),
+        "--exclude-larger-than".into(),
+        "1K".into(),
+        "--show-ignored-count".into(),
+    ]);
+    assert!(success);
+    assert!(stderr.contains("1 excluded"), "stderr: {}", stderr);
+
+    // An include pattern re-includes an oversized file
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--exclude-larger-than".into(),
+        "1K".into(),
+        "-I".into(),
+        "big.txt".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("big.txt"), "output: {}", output);
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--exclude-larger-than".into(),
+        "1K".into(),
+        "--explain".into(),
+        "big.txt".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("big.txt: larger than 1.0 KB (--exclude-larger-than) -> Exclude"),
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_max_chars_budget_counts_characters() {
+    // 10 lines of 6 characters (18 bytes each): 69 chars, 189 bytes
+    let content = ["日本語日本語"; 10].join("\n");
+    let (_tmp, root) = FixtureBuilder::new().file("ja.txt", &content).build();
+
+    let (output, _, success) =
+        run_tree2md([p(&root), "-c".into(), "--max-chars".into(), "70".into()]);
+    assert!(success);
+    assert!(
+        !output.contains("omitted"),
+        "69 chars fit a 70-char budget: {}",
+        output
+    );
+
+    let (output, _, success) =
+        run_tree2md([p(&root), "-c".into(), "--max-chars".into(), "30".into()]);
+    assert!(success);
+    // 4 lines = 27 chars fit, 5 lines = 34 do not
+    assert!(output.contains("(6 lines omitted)"), "output: {}", output);
+}
@@ -0,0 +1,581 @@
+mod fixtures;
+
+use fixtures::{p, run_tree2md, run_tree2md_with_env, FixtureBuilder};
+
+#[test]
+fn test_output_tty_forced_when_piped() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "tty".into()]);
+    assert!(success);
+    // Terminal renderer: box-drawing branches and LOC bars
+    assert!(output.contains('└'), "output: {}", output);
+    assert!(output.contains("[··········]"), "output: {}", output);
+    assert!(
+        !output.starts_with(".\n"),
+        "pipe root marker should be absent"
+    );
+}
+
+#[test]
+fn test_output_pipe_forced() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "pipe".into()]);
+    assert!(success);
+    assert!(output.starts_with(".\n"));
+    assert!(!output.contains("[··········]"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_output_md_table() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("a|b.txt", "x\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "md-table".into()]);
+    assert!(success);
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "| Path | Type | LOC | Size |");
+    assert_eq!(lines[1], "|------|------|----:|-----:|");
+    assert!(
+        output.contains("| a\\|b.txt | Text | 1 | 2 B |"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("| src/main.rs | Rust | 1 | 13 B |"),
+        "output: {}",
+        output
+    );
+    assert!(!output.contains("└──"), "no tree: {}", output);
+}
+
+/// Minimal well-formedness check: every open tag is closed in order
+fn assert_balanced_xml(xml: &str) {
+    let mut stack: Vec<String> = Vec::new();
+    for tag in xml.split('<').skip(1) {
+        let tag = &tag[..tag.find('>').expect("unterminated tag")];
+        if tag.starts_with('?') || tag.ends_with('/') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(stack.pop().as_deref(), Some(name), "xml: {}", xml);
+        } else {
+            stack.push(tag.split_whitespace().next().unwrap().to_string());
+        }
+    }
+    assert!(stack.is_empty(), "unclosed tags {:?}: {}", stack, xml);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_output_xml() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("R&D notes.txt", "x\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "xml".into()]);
+    assert!(success);
+    assert!(output.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<directory name=\""));
+    assert_balanced_xml(&output);
+    assert!(
+        output.contains("  <directory name=\"src\">\n    <file name=\"main.rs\" size=\"13\" loc=\"1\"/>\n  </directory>"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("<file name=\"R&amp;D notes.txt\""),
+        "output: {}",
+        output
+    );
+    assert!(!output.contains("R&D"), "raw ampersand leaked: {}", output);
+}
+
+#[test]
+fn test_output_gron() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/lib.rs", "pub fn lib() {}\n")
+        .file("src/main.rs", "fn main() {}\n")
+        .file("my-docs/guide.md", "# Guide\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "gron".into()]);
+    assert!(success);
+    assert!(output.starts_with("tree = {};\n"), "output: {}", output);
+    assert!(
+        output.contains("tree.dirs.src.files[1].name = \"main.rs\";\n"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("tree.dirs.src.files[1].loc = 1;\n"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("tree.dirs[\"my-docs\"].files[0].name = \"guide.md\";\n"),
+        "output: {}",
+        output
+    );
+    // Every line is an assignment
+    assert!(output
+        .lines()
+        .all(|l| l.starts_with("tree") && l.ends_with(';')));
+}
+
+#[test]
+fn test_output_gron_dir_named_files() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("a.txt", "a\n")
+        .file("files/b.txt", "b\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "gron".into()]);
+    assert!(success);
+    assert!(
+        output.contains("tree.files[0].name = \"a.txt\";\n"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("tree.dirs.files.files[0].name = \"b.txt\";\n"),
+        "output: {}",
+        output
+    );
+    // The directory must not reset the root's files array
+    assert!(!output.contains("tree.files = {};"), "output: {}", output);
+}
+
+#[test]
+fn test_structured_outputs_note_omitted_and_collapsed() {
+    let mut builder = FixtureBuilder::new()
+        .file("a.txt", "a\n")
+        .file("c.txt", "c\n")
+        .file("d.txt", "d\n");
+    for i in 0..5 {
+        builder = builder.file(format!("big/gen_{}.c", i), "int x;\n");
+    }
+    let (_tmp, root) = builder.build();
+
+    let run = |output: &str, extra: &[&str]| {
+        let mut args = vec![p(&root), "--output".into(), output.into()];
+        args.extend(extra.iter().map(|a| a.to_string()));
+        let (stdout, _, success) = run_tree2md(args);
+        assert!(success);
+        stdout
+    };
+    let capped = ["--max-children-per-dir", "2"];
+    let pruned = ["--prune-dirs-over", "4"];
+
+    let xml = run("xml", &capped);
+    assert!(xml.contains("omitted=\"2\">"), "output: {}", xml);
+    let xml = run("xml", &pruned);
+    assert!(
+        xml.contains("<directory name=\"big\" collapsed=\"5\">"),
+        "output: {}",
+        xml
+    );
+
+    let gron = run("gron", &capped);
+    assert!(gron.contains("tree.omitted = 2;\n"), "output: {}", gron);
+    let gron = run("gron", &pruned);
+    assert!(
+        gron.contains("tree.dirs.big.collapsed = 5;\n"),
+        "output: {}",
+        gron
+    );
+
+    let rst = run("rst", &capped);
+    assert!(rst.ends_with("- … 2 more\n"), "output: {}", rst);
+    let rst = run("rst", &pruned);
+    assert!(
+        rst.contains("- `big/ <big/>`__ (5 files, collapsed)\n"),
+        "output: {}",
+        rst
+    );
+
+    let table = run("md-table", &capped);
+    assert!(
+        table.contains("| ./ | … 2 more | - | - |"),
+        "output: {}",
+        table
+    );
+    let table = run("md-table", &pruned);
+    assert!(
+        table.contains("| big/ | 5 files, collapsed | - | - |"),
+        "output: {}",
+        table
+    );
+}
+
+#[test]
+fn test_output_toml_parses_back() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("README.md", "# Test\n")
+        .file("src/main.rs", "fn main() {}\n")
+        .file("my docs/guide.md", "# Guide\n")
+        .file("files/inner.txt", "x\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "toml".into()]);
+    assert!(success);
+
+    let doc: toml::Table = toml::from_str(&output).expect("valid TOML");
+    assert_eq!(doc["files"][0]["name"].as_str(), Some("README.md"));
+
+    let src = doc["dirs"]["src"].as_table().expect("nested table for src");
+    assert_eq!(src["files"][0]["name"].as_str(), Some("main.rs"));
+    assert_eq!(src["files"][0]["loc"].as_integer(), Some(1));
+
+    // Keys that are not bare identifiers are quoted
+    assert!(
+        output.contains("[[dirs.\"my docs\".files]]"),
+        "output: {}",
+        output
+    );
+    assert_eq!(
+        doc["dirs"]["my docs"]["files"][0]["name"].as_str(),
+        Some("guide.md")
+    );
+    // A directory named `files` stays apart from the files array
+    assert_eq!(
+        doc["dirs"]["files"]["files"][0]["name"].as_str(),
+        Some("inner.txt")
+    );
+}
+
+#[test]
+fn test_output_rst() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/util/mod.rs", "mod a;\n")
+        .file("src/main.rs", "fn main() {}\n")
+        .file("README.rst", "Title\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "rst".into()]);
+    assert!(success);
+    assert_eq!(
+        output,
+        "- `src/ <src/>`__\n\
+         \n  \
+         - `util/ <src/util/>`__\n\
+         \n    \
+         - `mod.rs <src/util/mod.rs>`__\n\
+         \n  \
+         - `main.rs <src/main.rs>`__\n\
+         \n\
+         - `README.rst <README.rst>`__\n"
+    );
+}
+
+#[test]
+fn test_output_rst_escapes_spaces_in_targets() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("my docs/read me.md", "# Hi\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--output".into(), "rst".into()]);
+    assert!(success);
+    assert!(
+        output.contains("- `my docs/ <my\\ docs/>`__\n"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("- `read me.md <my\\ docs/read\\ me.md>`__\n"),
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_tty_bars_align_with_emoji() {
+    use unicode_width::UnicodeWidthStr;
+
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("main.rs", "fn main() {}\n")
+        .file("data.qzx", "x\n")
+        .build();
+
+    // Unknown files get no emoji, so one row is emoji-prefixed and one is plain
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--output".into(),
+        "tty".into(),
+        "--fun".into(),
+        "on".into(),
+        "--emoji-for-unknown".into(),
+        "".into(),
+    ]);
+    assert!(success);
+
+    let bar_column = |needle: &str| {
+        let line = output.lines().find(|l| l.contains(needle)).unwrap();
+        line[..line.find('[').unwrap()].width()
+    };
+    assert!(output.contains("🦀"), "output: {}", output);
+    assert_eq!(
+        bar_column("main.rs"),
+        bar_column("data.qzx"),
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_tty_bars_align_across_depths() {
+    use unicode_width::UnicodeWidthStr;
+
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("a.rs", "fn a() {}\n")
+        .file("src/deeply/nested/module_with_long_name.rs", "fn b() {}\n")
+        .file("src/mid.rs", "fn c() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--output".into(),
+        "tty".into(),
+        "--fun".into(),
+        "on".into(),
+        "--depth-labels".into(),
+    ]);
+    assert!(success);
+
+    let bar_column = |needle: &str| {
+        let line = output.lines().find(|l| l.contains(needle)).unwrap();
+        line[..line.rfind('[').unwrap()].width()
+    };
+    let column = bar_column("a.rs");
+    assert_eq!(bar_column("mid.rs"), column, "output: {}", output);
+    assert_eq!(
+        bar_column("module_with_long_name.rs"),
+        column,
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_term_width_sets_bar_column() {
+    use unicode_width::UnicodeWidthStr;
+
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("a.rs", "fn a() {}\n")
+        .file("a_rather_long_file_name_for_alignment.rs", "fn b() {}\n")
+        .build();
+
+    let render = |width: &str| {
+        let (output, _, success) = run_tree2md([
+            p(&root),
+            "--output".into(),
+            "tty".into(),
+            "--fun".into(),
+            "off".into(),
+            "--term-width".into(),
+            width.into(),
+        ]);
+        assert!(success);
+        output
+    };
+    let bar_column = |output: &str, needle: &str| {
+        let line = output.lines().find(|l| l.contains(needle)).unwrap();
+        line[..line.find('[').unwrap()].width()
+    };
+
+    // Wide enough: bars align past the longest name
+    let wide = render("200");
+    assert_eq!(bar_column(&wide, "a.rs"), bar_column(&wide, "long_file"));
+
+    // Narrow: the column is pulled in so short rows fit, long names overflow it
+    let narrow = render("50");
+    assert!(bar_column(&narrow, "a.rs") < bar_column(&wide, "a.rs"));
+    assert!(bar_column(&narrow, "long_file") > bar_column(&narrow, "a.rs"));
+    let short_row = narrow.lines().find(|l| l.contains("a.rs")).unwrap();
+    assert!(short_row.width() <= 50, "output: {}", narrow);
+    assert_eq!(narrow, render("50"), "Forced width should be deterministic");
+}
+
+#[test]
+fn test_unknown_width_keeps_bars_aligned() {
+    use unicode_width::UnicodeWidthStr;
+
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("a.rs", "fn a() {}\n")
+        .file(
+            "a_file_name_long_enough_to_pass_the_assumed_eighty_columns.rs",
+            "fn b() {}\n",
+        )
+        .build();
+
+    // Piped output with no usable COLUMNS: the width is unknown, so the
+    // bar column is not pulled in to fit an assumed 80 columns
+    let (output, _, success) = run_tree2md_with_env(
+        [
+            p(&root),
+            "--output".into(),
+            "tty".into(),
+            "--fun".into(),
+            "off".into(),
+        ],
+        &[("COLUMNS", "")],
+    );
+    assert!(success);
+    let bar_column = |needle: &str| {
+        let line = output.lines().find(|l| l.contains(needle)).unwrap();
+        line[..line.find('[').unwrap()].width()
+    };
+    assert_eq!(
+        bar_column("a.rs"),
+        bar_column("eighty"),
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_deterministic_output_is_reproducible() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("src/lib.rs", "pub fn lib() {}\n")
+        .file("docs/guide.md", "# Guide\n")
+        .file("Cargo.toml", "[package]\n")
+        .build();
+
+    let run = |envs: &[(&str, &str)]| {
+        let (output, _, success) = run_tree2md_with_env(
+            [
+                p(&root),
+                "--deterministic".into(),
+                "--output".into(),
+                "tty".into(),
+                "--depth-colors".into(),
+                "--stats".into(),
+                "full".into(),
+            ],
+            envs,
+        );
+        assert!(success);
+        output
+    };
+
+    let first = run(&[("COLUMNS", "200"), ("FORCE_COLOR", "1")]);
+    let second = run(&[("COLUMNS", "40")]);
+    assert_eq!(first, second);
+    assert!(!first.contains('\x1b'), "colors should be off: {:?}", first);
+}
+
+#[test]
+fn test_rename_root() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--rename-root".into(),
+        "my-project".into(),
+        "--render-root-as-heading".into(),
+        "2".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.starts_with("## my-project\n\nmy-project\n└── src/"),
+        "output: {}",
+        output
+    );
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--rename-root".into(),
+        "my-project".into(),
+        "--output".into(),
+        "xml".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("<directory name=\"my-project\">"),
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_tty_depth_styles_follow_nesting() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("top.rs", "fn top() {}\n")
+        .file("src/deep/er/mod.rs", "fn deep() {}\n")
+        .build();
+    let run = |extra: &[&str]| {
+        let mut args = vec![p(&root), "--output".into(), "tty".into()];
+        args.extend(extra.iter().map(|a| a.to_string()));
+        let (output, _, success) = run_tree2md_with_env(args, &[("FORCE_COLOR", "1")]);
+        assert!(success);
+        output
+    };
+    let line = |output: &str, needle: &str| {
+        output
+            .lines()
+            .find(|l| l.contains(needle))
+            .unwrap()
+            .to_string()
+    };
+
+    // Labels count levels, whatever the width of the tree connectors
+    let output = run(&["--depth-labels"]);
+    assert!(
+        line(&output, "src/").contains("[L0] "),
+        "output: {}",
+        output
+    );
+    assert!(
+        line(&output, "deep/").contains("[L1] "),
+        "output: {}",
+        output
+    );
+    assert!(
+        line(&output, "mod.rs").contains("[L3] "),
+        "output: {}",
+        output
+    );
+
+    // Each level gets its own branch color
+    let output = run(&["--depth-colors"]);
+    assert!(
+        line(&output, "src/").contains("\x1b[36m"),
+        "output: {:?}",
+        output
+    );
+    assert!(
+        line(&output, "deep/").contains("\x1b[32m"),
+        "output: {:?}",
+        output
+    );
+    assert!(
+        !line(&output, "deep/").contains("\x1b[36m"),
+        "output: {:?}",
+        output
+    );
+
+    // --color-by depth colors names the same way
+    let output = run(&["--color-by", "depth"]);
+    assert!(
+        line(&output, "top.rs").contains("\x1b[36m"),
+        "output: {:?}",
+        output
+    );
+    assert!(
+        line(&output, "mod.rs").contains("\x1b[35m"),
+        "output: {:?}",
+        output
+    );
+}
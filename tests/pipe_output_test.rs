@@ -155,6 +155,51 @@ fn test_pipe_contents_lang_hint() {
     assert!(output.contains("```json"), "Should detect JSON");
 }
 
+#[test]
+fn test_pipe_contents_encoding() {
+    let (_tmp, root) = FixtureBuilder::new().file("utf8.txt", "plain\n").build();
+    // "café" in Latin-1: 0xE9 is not valid UTF-8 on its own
+    std::fs::write(root.join("latin1.txt"), b"caf\xe9\n").unwrap();
+
+    // Default: non-UTF-8 files are left out of the contents
+    let (output, _, success) = run_tree2md([p(&root), "-c".into()]);
+    assert!(success);
+    assert!(output.contains("## utf8.txt"));
+    assert!(!output.contains("## latin1.txt"), "output: {}", output);
+
+    let (output, _, success) =
+        run_tree2md([p(&root), "-c".into(), "--encoding".into(), "latin1".into()]);
+    assert!(success);
+    assert!(
+        output.contains("## latin1.txt\n\n(not valid UTF-8; decoded as Latin-1)\n\n```\ncafé\n```"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("## utf8.txt\n\n```\nplain\n"),
+        "No note for UTF-8"
+    );
+
+    let (output, _, _) = run_tree2md([p(&root), "-c".into(), "--encoding".into(), "lossy".into()]);
+    assert!(output.contains("caf\u{FFFD}\n"), "output: {}", output);
+}
+
+#[test]
+fn test_pipe_contents_encoding_skips_binaries() {
+    let (_tmp, root) = FixtureBuilder::new().file("notes", "plain\n").build();
+    // No extension to give it away; the content sniff has to catch it
+    std::fs::write(root.join("a.out"), b"ELF\0\x01\x02\xff").unwrap();
+
+    for encoding in ["latin1", "lossy"] {
+        let (output, _, success) =
+            run_tree2md([p(&root), "-c".into(), "--encoding".into(), encoding.into()]);
+        assert!(success);
+        assert!(output.contains("## notes"), "output: {}", output);
+        assert!(!output.contains("## a.out"), "output: {}", output);
+        assert!(!output.contains('\0'), "output: {:?}", output);
+    }
+}
+
 #[test]
 fn test_pipe_no_emoji() {
     let (_tmp, root) = FixtureBuilder::new()
@@ -187,3 +232,425 @@ fn test_pipe_stats_present() {
         "Should show stats by default"
     );
 }
+
+#[test]
+fn test_pipe_dim_generated() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("yarn.lock", "# lockfile\n")
+        .file("app.min.js", "x\n")
+        .file("main.rs", "fn main() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--dim-generated".into()]);
+    assert!(success);
+    assert!(output.contains("yarn.lock  (generated)"));
+    assert!(output.contains("app.min.js  (1 lines)  (generated)"));
+    assert!(!output.contains("main.rs  (1 lines)  (generated)"));
+
+    // A custom glob replaces the default set
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--dim-generated".into(),
+        "--generated-glob".into(),
+        "*.rs".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("main.rs  (1 lines)  (generated)"));
+    assert!(!output.contains("yarn.lock  (generated)"));
+
+    // Without the flag nothing is annotated
+    let (output, _, _) = run_tree2md([p(&root)]);
+    assert!(!output.contains("(generated)"));
+}
+
+#[test]
+fn test_pipe_max_name_length() {
+    let long_name = format!("{}.rs", "a".repeat(37));
+    assert_eq!(long_name.len(), 40);
+    let (_tmp, root) = FixtureBuilder::new()
+        .file(&long_name, "fn main() {}\n")
+        .file("short.rs", "fn short() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--max-name-length".into(),
+        "15".into(),
+        "--loc".into(),
+        "off".into(),
+    ]);
+    assert!(success);
+    assert!(!output.contains(&long_name));
+    assert!(output.contains("aaaaaaaaaa…a.rs"), "output: {}", output);
+    assert!(output.contains("short.rs"));
+}
+
+#[test]
+fn test_pipe_strip_extension() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file(".gitignore", "target\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--strip-extension".into(), "-c".into()]);
+    assert!(success);
+    assert!(output.contains("└── main  (1 lines)"), "output: {}", output);
+    assert!(!output.contains("main.rs  (1 lines)"), "output: {}", output);
+    // Dotfiles have no extension to strip
+    assert!(
+        output.contains(".gitignore  (1 lines)"),
+        "output: {}",
+        output
+    );
+    // The path behind the label keeps its extension
+    assert!(output.contains("## src/main.rs\n"), "output: {}", output);
+}
+
+#[test]
+fn test_pipe_highlight() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("src/lib.rs", "pub fn lib() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--highlight".into(), "main.rs".into()]);
+    assert!(success);
+    assert!(
+        output.contains("└── **main.rs**  (1 lines)"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("├── lib.rs  (1 lines)"),
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_pipe_flatten_depth() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("README.md", "# Test\n")
+        .file("src/main.rs", "fn main() {}\n")
+        .file("src/util/mod.rs", "mod io;\n")
+        .file("src/util/io/read.rs", "fn read() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--flatten-depth".into(),
+        "1".into(),
+        "--loc".into(),
+        "off".into(),
+        "--stats".into(),
+        "off".into(),
+    ]);
+    assert!(success);
+    assert_eq!(
+        output,
+        ".\n\
+         ├── src/\n\
+         │   ├── main.rs\n\
+         │   ├── util/io/read.rs\n\
+         │   └── util/mod.rs\n\
+         └── README.md\n"
+    );
+}
+
+#[test]
+fn test_pipe_group_dirs_last() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("Cargo.toml", "[package]\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--stats".into(), "off".into()]);
+    assert!(success);
+    assert!(output.find("src/").unwrap() < output.find("Cargo.toml").unwrap());
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--stats".into(),
+        "off".into(),
+        "--group-dirs-last".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.find("Cargo.toml").unwrap() < output.find("src/").unwrap(),
+        "files should precede directories: {}",
+        output
+    );
+    assert!(output.contains("├── Cargo.toml"));
+    assert!(output.contains("└── src/"));
+}
+
+#[test]
+fn test_pipe_trailing_slash() {
+    let (_tmp, root) = FixtureBuilder::new().file("foo/bar.txt", "bar\n").build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--trailing-slash".into(), "always".into()]);
+    assert!(success);
+    assert!(output.contains("└── foo/\n"), "output: {}", output);
+
+    let (output, _, success) = run_tree2md([p(&root), "--trailing-slash".into(), "never".into()]);
+    assert!(success);
+    assert!(output.contains("└── foo\n"), "output: {}", output);
+    assert!(!output.contains("foo/"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_pipe_classify_suffixes() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("run.sh", "#!/bin/sh\n")
+        .file("notes.txt", "notes\n")
+        .file("src/lib.rs", "pub fn lib() {}\n")
+        .build();
+    let script = root.join("run.sh");
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::os::unix::fs::symlink("notes.txt", root.join("link.txt")).unwrap();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--classify".into(),
+        "--show-symlinks".into(),
+        "--loc".into(),
+        "off".into(),
+        "--trailing-slash".into(),
+        "never".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("run.sh*\n"), "output: {}", output);
+    assert!(output.contains("notes.txt\n"), "output: {}", output);
+    assert!(
+        output.contains("link.txt@ -> notes.txt"),
+        "output: {}",
+        output
+    );
+    assert!(output.contains("src/\n"), "output: {}", output);
+
+    // Without --classify no indicators are added
+    let (output, _, _) = run_tree2md([p(&root), "--loc".into(), "off".into()]);
+    assert!(output.contains("run.sh\n"));
+}
+
+#[test]
+fn test_pipe_root_as_heading() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("myproject/main.rs", "fn main() {}\n")
+        .build();
+    let project = root.join("myproject");
+
+    let (output, _, success) =
+        run_tree2md([p(&project), "--render-root-as-heading".into(), "2".into()]);
+    assert!(success);
+    assert!(
+        output.starts_with("## myproject\n\n.\n"),
+        "output: {}",
+        output
+    );
+
+    let (_, _, success) = run_tree2md([p(&project), "--render-root-as-heading".into(), "7".into()]);
+    assert!(!success, "heading levels above 6 are rejected");
+}
+
+#[test]
+fn test_pipe_root_emoji() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("myproject/main.rs", "fn main() {}\n")
+        .build();
+    let project = root.join("myproject");
+
+    let (output, _, success) = run_tree2md([
+        p(&project),
+        "--render-root-as-heading".into(),
+        "1".into(),
+        "--root-emoji".into(),
+        "🚀".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.starts_with("# 🚀 myproject\n\n.\n"),
+        "output: {}",
+        output
+    );
+
+    let (_, _, success) = run_tree2md([p(&project), "--root-emoji".into(), "🚀".into()]);
+    assert!(!success, "--root-emoji needs a heading to decorate");
+}
+
+#[test]
+fn test_pipe_annotate_from() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("src/lib.rs", "pub fn lib() {}\n")
+        .file(
+            "notes.txt",
+            "src/: core library\nsrc/main.rs: entry point\n",
+        )
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--loc".into(),
+        "off".into(),
+        "--annotate-from".into(),
+        p(root.join("notes.txt")),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("src/  — core library"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("main.rs  — entry point"),
+        "output: {}",
+        output
+    );
+    assert!(output.contains("lib.rs\n"), "undescribed paths stay bare");
+}
+
+#[test]
+fn test_pipe_annotate_from_missing_file_warns_once() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+
+    // --fail-on-empty collects stats before rendering, building the IR twice
+    let (_, stderr, success) = run_tree2md([
+        p(&root),
+        "--fail-on-empty".into(),
+        "--annotate-from".into(),
+        p(root.join("missing.txt")),
+    ]);
+    assert!(success);
+    assert_eq!(
+        stderr.matches("Failed to load annotations").count(),
+        1,
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_pipe_contents_lang_override() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("setup.example", "export API_KEY=changeme\n")
+        .file("page.tpl", "<p>hi</p>\n")
+        .file("main.rs", "fn main() {}\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "-c".into(),
+        "--contents-lang".into(),
+        "setup.example=bash".into(),
+        "--contents-lang".into(),
+        "*.tpl=html".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("## setup.example\n\n```bash\n"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("## page.tpl\n\n```html\n"),
+        "output: {}",
+        output
+    );
+    assert!(output.contains("## main.rs\n\n```rust\n"));
+
+    let (_, _, success) = run_tree2md([
+        p(&root),
+        "-c".into(),
+        "--contents-lang".into(),
+        "bash".into(),
+    ]);
+    assert!(!success, "missing '=' is rejected");
+}
+
+#[test]
+fn test_depth_labels() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/cli/main.rs", "fn main() {}\n")
+        .file("README.md", "# readme\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--depth-labels".into()]);
+    assert!(success);
+    assert!(output.contains("[L0] src/"), "output: {}", output);
+    assert!(output.contains("[L1] cli/"), "output: {}", output);
+    assert!(output.contains("[L2] main.rs"), "output: {}", output);
+    assert!(output.contains("[L0] README.md"), "output: {}", output);
+
+    let (output, _, _) = run_tree2md([p(&root)]);
+    assert!(!output.contains("[L"), "labels are opt-in: {}", output);
+}
+
+#[test]
+fn test_max_file_size_for_loc_skips_large_files() {
+    let big = "line\n".repeat(1000); // 5000 bytes
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("big.log.txt", &big)
+        .file("small.txt", "one\ntwo\n")
+        .build();
+
+    let (output, _, success) =
+        run_tree2md([p(&root), "--max-file-size-for-loc".into(), "1K".into()]);
+    assert!(success);
+    let big_line = output.lines().find(|l| l.contains("big.log.txt")).unwrap();
+    assert!(!big_line.contains("lines)"), "output: {}", output);
+    assert!(
+        big_line.contains("(too large to count)"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("small.txt  (2 lines)"),
+        "output: {}",
+        output
+    );
+
+    // Without the flag the file is counted as usual
+    let (output, _, _) = run_tree2md([p(&root)]);
+    assert!(
+        output.contains("big.log.txt  (1000 lines)"),
+        "output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_group_tests() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("api/server.go", "package api\n")
+        .file("api/server_test.go", "package api\n")
+        .file("api/client_test.go", "package api\n")
+        .file("py/app.py", "print()\n")
+        .file("py/test_app.py", "def test(): pass\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--group-tests".into(),
+        "--loc".into(),
+        "off".into(),
+    ]);
+    assert!(success);
+
+    let expected_api = "├── api/\n\
+                        │   ├── tests (2)/\n\
+                        │   │   ├── client_test.go\n\
+                        │   │   └── server_test.go\n\
+                        │   └── server.go";
+    assert!(output.contains(expected_api), "output: {}", output);
+    let expected_py = "└── py/\n    \
+                       ├── tests (1)/\n    \
+                       │   └── test_app.py\n    \
+                       └── app.py";
+    assert!(output.contains(expected_py), "output: {}", output);
+}
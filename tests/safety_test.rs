@@ -321,3 +321,61 @@ fn test_package_lock_files_included() {
     assert!(output.contains("Cargo.lock"));
     assert!(output.contains("yarn.lock"));
 }
+
+#[test]
+fn test_hide_lockfiles() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("Cargo.lock", "[[package]]")
+        .file("yarn.lock", "# yarn")
+        .file("Cargo.toml", "[package]")
+        .build();
+
+    let (output, _, success) = run_tree2md([p(&root), "--hide-lockfiles".into()]);
+    assert!(success);
+
+    assert!(!output.contains("Cargo.lock"));
+    assert!(!output.contains("yarn.lock"));
+    assert!(output.contains("Cargo.toml"));
+}
+
+#[test]
+fn test_hide_lockfiles_goes_through_matcher() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("Cargo.lock", "[[package]]")
+        .file("yarn.lock", "# yarn")
+        .file("Cargo.toml", "[package]")
+        .build();
+
+    // Counted as hidden
+    let (_, stderr, success) = run_tree2md([
+        p(&root),
+        "--hide-lockfiles".into(),
+        "--show-ignored-count".into(),
+    ]);
+    assert!(success);
+    assert!(stderr.contains("2 excluded"), "stderr: {}", stderr);
+
+    // An include pattern re-includes a lockfile
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--hide-lockfiles".into(),
+        "-I".into(),
+        "Cargo.lock".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("Cargo.lock"), "output: {}", output);
+
+    // --explain names the flag
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--hide-lockfiles".into(),
+        "--explain".into(),
+        "yarn.lock".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("yarn.lock: lockfile (--hide-lockfiles) -> Exclude"),
+        "output: {}",
+        output
+    );
+}
@@ -0,0 +1,77 @@
+mod fixtures;
+
+use fixtures::{p, run_tree2md, FixtureBuilder};
+use std::fs;
+
+#[test]
+fn test_split_output_writes_per_dir_files_and_index() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("tests/it.rs", "#[test]\nfn it() {}\n")
+        .file("README.md", "# Test\n")
+        .build();
+    let out = tempfile::TempDir::new().unwrap();
+    let out_dir = out.path().join("docs");
+
+    let (stdout, stderr, success) = run_tree2md([p(&root), "--split-output".into(), p(&out_dir)]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.is_empty(), "split mode should not print the tree");
+
+    let src = fs::read_to_string(out_dir.join("src.md")).unwrap();
+    assert!(src.starts_with("# src/\n"));
+    assert!(src.contains("main.rs"));
+    assert!(!src.contains("it.rs"));
+
+    let tests = fs::read_to_string(out_dir.join("tests.md")).unwrap();
+    assert!(tests.contains("it.rs"));
+
+    let index = fs::read_to_string(out_dir.join("index.md")).unwrap();
+    assert!(index.contains("- [src/](src.md)"));
+    assert!(index.contains("- [tests/](tests.md)"));
+    assert!(index.contains("- README.md"));
+}
+
+#[test]
+fn test_split_output_avoids_index_collision() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("index/page.md", "# Page\n")
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+    let out = tempfile::TempDir::new().unwrap();
+
+    let (_, stderr, success) = run_tree2md([p(&root), "--split-output".into(), p(out.path())]);
+    assert!(success, "stderr: {}", stderr);
+
+    let index_dir = fs::read_to_string(out.path().join("index-1.md")).unwrap();
+    assert!(index_dir.contains("page.md"), "{}", index_dir);
+
+    let index = fs::read_to_string(out.path().join("index.md")).unwrap();
+    assert!(index.contains("- [index/](index-1.md)"), "{}", index);
+    assert!(index.contains("- [src/](src.md)"), "{}", index);
+
+    // Each split tree is rooted at its directory, not a bare `.`
+    let src = fs::read_to_string(out.path().join("src.md")).unwrap();
+    assert!(src.contains("\n\nsrc\n└── main.rs"), "{}", src);
+}
+
+#[test]
+fn test_split_output_rename_root_only_names_index() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+    let out = tempfile::TempDir::new().unwrap();
+
+    let (_, stderr, success) = run_tree2md([
+        p(&root),
+        "--split-output".into(),
+        p(out.path()),
+        "--rename-root".into(),
+        "Proj".into(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    let index = fs::read_to_string(out.path().join("index.md")).unwrap();
+    assert!(index.starts_with("# Proj\n"), "{}", index);
+    let src = fs::read_to_string(out.path().join("src.md")).unwrap();
+    assert!(!src.contains("Proj"), "{}", src);
+}
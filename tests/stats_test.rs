@@ -20,6 +20,31 @@ fn test_stats_off() {
     assert!(!output.contains("files"), "Should not show file count");
 }
 
+#[test]
+fn test_no_stats_deprecated_alias() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("main.rs", "fn main() {}")
+        .file("lib.rs", "pub fn lib() {}")
+        .build();
+
+    let (output, stderr, success) = run_tree2md([
+        p(&root),
+        "--stats".into(),
+        "full".into(),
+        "--no-stats".into(),
+    ]);
+    assert!(success);
+
+    // --no-stats wins over an explicit --stats mode
+    assert!(!output.contains("Totals"), "Should not show totals");
+    assert!(!output.contains("Stats"), "Should not show stats");
+    assert!(
+        stderr.contains("--no-stats is deprecated"),
+        "Should print a deprecation warning, got: {}",
+        stderr
+    );
+}
+
 #[test]
 fn test_stats_min() {
     let (_tmp, root) = FixtureBuilder::new()
@@ -116,12 +141,12 @@ fn test_stats_with_many_files() {
 
     // Create many Rust files
     for i in 0..10 {
-        builder = builder.file(&format!("rust_{}.rs", i), "fn main() {}");
+        builder = builder.file(format!("rust_{}.rs", i), "fn main() {}");
     }
 
     // Create a few Python files
     for i in 0..3 {
-        builder = builder.file(&format!("python_{}.py", i), "print('hello')");
+        builder = builder.file(format!("python_{}.py", i), "print('hello')");
     }
 
     // Create one JavaScript file
@@ -228,7 +253,7 @@ fn test_loc_skip_binary_files() {
         .file("text.txt", "Hello\nWorld\n")
         .file(
             "binary.jpg",
-            &[0xFFu8, 0xD8, 0xFF, 0xE0, 0x00, 0x10, 0x4A, 0x46, 0x49, 0x46]
+            [0xFFu8, 0xD8, 0xFF, 0xE0, 0x00, 0x10, 0x4A, 0x46, 0x49, 0x46]
                 .iter()
                 .map(|&b| b as char)
                 .collect::<String>(),
@@ -277,3 +302,204 @@ fn test_stats_directory_count() {
         "Should count files correctly"
     );
 }
+
+#[test]
+fn test_stats_output_is_deterministic() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("main.rs", "fn main() {}\n")
+        .file("app.py", "print(1)\n")
+        .file("main.go", "package main\n")
+        .file("index.js", "x\n")
+        .file("style.css", "a {}\n")
+        .file("README.md", "# Test\n")
+        .file("Cargo.toml", "[package]\n")
+        .build();
+
+    let (first, _, success) = run_tree2md([p(&root)]);
+    assert!(success);
+    for _ in 0..5 {
+        let (again, _, _) = run_tree2md([p(&root)]);
+        assert_eq!(first, again, "output should be byte-identical across runs");
+    }
+}
+
+#[test]
+fn test_stats_threshold_rolls_up_rare_types() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("a.rs", "fn a() {}\n")
+        .file("b.rs", "fn b() {}\n")
+        .file("c.rs", "fn c() {}\n")
+        .file("app.py", "print(1)\n")
+        .file("main.go", "package main\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--stats".into(),
+        "full".into(),
+        "--stats-threshold".into(),
+        "2".into(),
+    ]);
+    assert!(success);
+    assert!(output.contains("- Rust: 3"), "output: {}", output);
+    assert!(
+        output.contains("- Other (2 types): 2 (40%)"),
+        "output: {}",
+        output
+    );
+    assert!(!output.contains("- Python:"));
+    assert!(!output.contains("- Go:"));
+
+    // Percent form: 30% keeps only Rust (60%)
+    let (output, _, _) = run_tree2md([p(&root), "--stats-threshold".into(), "30%".into()]);
+    assert!(output.contains("- Other (2 types)"), "output: {}", output);
+
+    let (_, _, success) = run_tree2md([p(&root), "--stats-threshold".into(), "lots".into()]);
+    assert!(!success);
+}
+
+#[test]
+fn test_aggregate_by_dir() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("src/util/mod.rs", "mod a;\nmod b;\n")
+        .file("docs/guide.md", "# Guide\n\nText\n")
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--aggregate-by".into(),
+        "dir".into(),
+        "--stats".into(),
+        "off".into(),
+    ]);
+    assert!(success);
+
+    // Each directory line is followed by its subtree's file and line counts
+    assert!(
+        output.contains("├── docs/\n│   (1 files, 3 lines)\n"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("└── src/\n    (2 files, 3 lines)\n"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("    ├── util/\n    │   (1 files, 2 lines)\n"),
+        "output: {}",
+        output
+    );
+
+    // Default keeps the tree free of subtotals
+    let (output, _, _) = run_tree2md([p(&root)]);
+    assert!(!output.contains("files, "), "output: {}", output);
+}
+
+#[test]
+fn test_count_badge_threshold() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .files_with((0..2).map(|i| format!("small/f{}.txt", i)), |_| {
+            "x\n".into()
+        })
+        .files_with((0..15).map(|i| format!("large/f{:02}.txt", i)), |_| {
+            "x\n".into()
+        })
+        .build();
+
+    let (output, _, success) = run_tree2md([
+        p(&root),
+        "--aggregate-by".into(),
+        "dir".into(),
+        "--count-badge-threshold".into(),
+        "10".into(),
+        "--stats".into(),
+        "off".into(),
+    ]);
+    assert!(success);
+    assert!(
+        output.contains("├── large/\n│   (15 files, 15 lines)\n"),
+        "output: {}",
+        output
+    );
+    assert!(
+        output.contains("└── small/\n    ├── f0.txt"),
+        "output: {}",
+        output
+    );
+    assert!(!output.contains("(2 files"), "output: {}", output);
+}
+
+#[test]
+fn test_count_badge_threshold_requires_aggregate_by_dir() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+
+    let (_, stderr, success) =
+        run_tree2md([p(&root), "--count-badge-threshold".into(), "10".into()]);
+    assert!(!success, "threshold without --aggregate-by dir should fail");
+    assert!(stderr.contains("--aggregate-by dir"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_require_predicates() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .file("src/lib.rs", "pub fn lib() {}\n")
+        .file("README.md", "# Test\n")
+        .build();
+
+    let (output, stderr, success) = run_tree2md([
+        p(&root),
+        "--require".into(),
+        "files>=3".into(),
+        "--require".into(),
+        ".rs==2".into(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(output.contains("main.rs"));
+
+    let (output, stderr, success) = run_tree2md([
+        p(&root),
+        "--require".into(),
+        "files>=10".into(),
+        "--require".into(),
+        "dirs>=1".into(),
+    ]);
+    assert!(!success);
+    assert!(output.is_empty());
+    assert!(
+        stderr.contains("files>=10 not met (actual: 3)"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!stderr.contains("dirs"), "stderr: {}", stderr);
+
+    let (_, stderr, success) = run_tree2md([p(&root), "--require".into(), "size>1".into()]);
+    assert!(!success);
+    assert!(stderr.contains("invalid requirement"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_no_empty_stats() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}\n")
+        .build();
+    let run = |extra: &[&str]| {
+        let mut args = vec![p(&root), "-I".into(), "*.nomatch".into()];
+        args.extend(extra.iter().map(|a| a.to_string()));
+        let (output, _, success) = run_tree2md(args);
+        assert!(success);
+        output
+    };
+
+    assert!(run(&[]).contains("Totals"));
+    let output = run(&["--no-empty-stats"]);
+    assert!(!output.contains("Totals"), "output: {}", output);
+
+    // Stats still show when files remain
+    let (output, _, _) = run_tree2md([p(&root), "--no-empty-stats".into()]);
+    assert!(output.contains("Totals"), "output: {}", output);
+}
@@ -0,0 +1,105 @@
+mod fixtures;
+
+use fixtures::{p, run_tree2md, FixtureBuilder};
+
+#[test]
+fn test_root_flag_matches_positional_target() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .file("src/lib.rs", "pub fn lib() {}")
+        .file("README.md", "# Test")
+        .build();
+    let src = root.join("src");
+
+    let (positional, _, success) = run_tree2md([p(&src)]);
+    assert!(success);
+
+    let (flag, _, success) = run_tree2md(["--root".into(), p(&src)]);
+    assert!(success);
+
+    assert_eq!(
+        positional, flag,
+        "--root should behave like positional TARGET"
+    );
+    assert!(flag.contains("main.rs"));
+    assert!(!flag.contains("README.md"));
+}
+
+#[test]
+fn test_root_flag_conflicting_target_fails() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .file("docs/guide.md", "# Guide")
+        .build();
+
+    let (_, stderr, success) =
+        run_tree2md([p(root.join("src")), "--root".into(), p(root.join("docs"))]);
+    assert!(!success, "Differing TARGET and --root should fail");
+    assert!(stderr.contains("--root"), "stderr: {}", stderr);
+
+    // Same directory given twice is fine
+    let (_, _, success) = run_tree2md([p(root.join("src")), "--root".into(), p(root.join("src"))]);
+    assert!(success);
+}
+
+#[test]
+fn test_multiple_targets_share_common_ancestor() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/main.rs", "fn main() {}")
+        .file("docs/guide.md", "# Guide")
+        .file("README.md", "# Test")
+        .build();
+
+    let (stdout, stderr, success) = run_tree2md([p(root.join("src")), p(root.join("docs"))]);
+    assert!(success, "stderr: {}", stderr);
+    assert!(stdout.contains("src/"), "stdout: {}", stdout);
+    assert!(stdout.contains("main.rs"));
+    assert!(stdout.contains("docs/"));
+    assert!(stdout.contains("guide.md"));
+    assert!(
+        !stdout.contains("README.md"),
+        "Files outside the targets should not appear: {}",
+        stdout
+    );
+
+    // --root names a single directory
+    let (_, _, success) = run_tree2md([
+        p(root.join("src")),
+        p(root.join("docs")),
+        "--root".into(),
+        p(root.join("src")),
+    ]);
+    assert!(!success);
+}
+
+#[test]
+fn test_multiple_targets_sample_and_hidden_count_once() {
+    let (_tmp, root) = FixtureBuilder::new()
+        .file("src/a.rs", "")
+        .file("src/b.rs", "")
+        .file("src/c.rs", "")
+        .file("docs/a.md", "")
+        .file("docs/b.md", "")
+        .file("docs/c.md", "")
+        .build();
+
+    let (stdout, stderr, success) = run_tree2md([
+        p(root.join("src")),
+        p(root.join("docs")),
+        "--sample".into(),
+        "2".into(),
+        "--show-ignored-count".into(),
+        "--stats".into(),
+        "off".into(),
+    ]);
+    assert!(success, "stderr: {}", stderr);
+
+    // The sample covers the merged tree, not each target
+    let files = stdout
+        .lines()
+        .filter(|l| l.contains(".rs") || l.contains(".md"))
+        .count();
+    assert_eq!(files, 2, "stdout: {}", stdout);
+
+    assert_eq!(stderr.matches("Hidden:").count(), 1, "stderr: {}", stderr);
+}